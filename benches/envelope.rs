@@ -0,0 +1,212 @@
+//! Allocations and time to decode a large `/pse` page, comparing the old `Value` re-shaping
+//! with single-pass [`ApiEnvelope`] decoding, first on the body alone and then through the
+//! client against a local server, as `CufinderSDK::pse` decodes it.
+//!
+//! Run with `cargo bench --bench envelope`.
+
+use cufinder_rust::{
+    ApiEnvelope, ClientConfig, CufinderSDK, Endpoint, PseParams, PseResponse, endpoint::Pse,
+};
+use serde_json::{Value, json};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+struct Counting;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(new_size as u64, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const PEOPLE: usize = 1_000;
+const ITERATIONS: u32 = 50;
+
+fn page() -> String {
+    let peoples: Vec<Value> = (0..PEOPLE)
+        .map(|i| {
+            json!({
+                "first_name": format!("First{}", i),
+                "last_name": format!("Last{}", i),
+                "full_name": format!("First{} Last{}", i, i),
+                "overview": "Engineering leader with a long career in distributed systems.",
+                "connections": 500,
+                "skills": ["rust", "go", "kubernetes", "leadership"],
+                "experiences": [
+                    { "title": "Engineer", "company_name": "Acme", "start_date": "2019-01" },
+                    { "title": "Lead", "company_name": "Globex", "start_date": "2022-06" }
+                ],
+                "location": { "country": "United States", "state": "California", "city": "San Francisco" },
+                "current_job": { "title": "Lead", "role": "engineering", "level": "manager" },
+                "social": { "linkedin": format!("linkedin.com/in/person-{}", i) }
+            })
+        })
+        .collect();
+    json!({
+        "status": 1,
+        "data": { "confidence_level": 90, "credit_count": 1, "query": {}, "peoples": peoples },
+        "meta_data": { "page": 1, "total": 25_000 }
+    })
+    .to_string()
+}
+
+/// The decoding done before `ApiEnvelope`: parse to a `Value`, clone `data`, merge
+/// `meta_data` into it, then deserialize the typed response from the `Value`
+fn reshape(body: &str) -> PseResponse {
+    let mut response: Value = serde_json::from_str(body).unwrap();
+    if let Some(data) = response.get("data") {
+        let mut data = data.clone();
+        if let (Value::Object(map), Some(meta_data)) = (&mut data, response.get("meta_data")) {
+            map.insert("meta_data".to_string(), meta_data.clone());
+        }
+        response = data;
+    }
+    serde_json::from_value(response).unwrap()
+}
+
+fn envelope(body: &str) -> PseResponse {
+    Pse::from_envelope(ApiEnvelope::from_body(body).unwrap())
+}
+
+fn measure(name: &str, body: &str, decode: fn(&str) -> PseResponse) {
+    assert_eq!(decode(body).peoples.len(), PEOPLE);
+    report(name, || {
+        for _ in 0..ITERATIONS {
+            black_box(decode(black_box(body)));
+        }
+    });
+}
+
+/// Run `run`, which makes `ITERATIONS` decodes, and print its allocations and time per page
+fn report(name: &str, run: impl FnOnce()) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+    let started = Instant::now();
+    run();
+    let elapsed = started.elapsed() / ITERATIONS;
+    let allocations = (ALLOCATIONS.load(Ordering::Relaxed) - allocations) / ITERATIONS as u64;
+    let bytes = (BYTES.load(Ordering::Relaxed) - bytes) / ITERATIONS as u64;
+    println!(
+        "{:<10} {:>9} allocations {:>12} bytes {:>10.2?} per page",
+        name, allocations, bytes, elapsed
+    );
+}
+
+/// Answer every request on a local port with `body`, over keep-alive connections
+fn serve(body: String) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let response = format!(
+        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+        body.len(),
+        body
+    );
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let response = response.clone();
+            std::thread::spawn(move || {
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                loop {
+                    let mut length = 0;
+                    loop {
+                        line.clear();
+                        if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                            return;
+                        }
+                        if let Some(value) =
+                            line.to_ascii_lowercase().strip_prefix("content-length:")
+                        {
+                            length = value.trim().parse().unwrap();
+                        }
+                        if line == "\r\n" {
+                            break;
+                        }
+                    }
+                    reader
+                        .by_ref()
+                        .take(length)
+                        .read_to_end(&mut Vec::new())
+                        .unwrap();
+                    stream.write_all(response.as_bytes()).unwrap();
+                }
+            });
+        }
+    });
+    url
+}
+
+fn main() {
+    let body = page();
+    println!("/pse page of {} people, {} bytes", PEOPLE, body.len());
+    measure("reshape", &body, reshape);
+    measure("envelope", &body, envelope);
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let sdk = CufinderSDK::with_config(ClientConfig {
+        api_key: "bench-api-key".to_string(),
+        base_url: serve(body),
+        timeout: Duration::from_secs(30),
+        max_retries: 0,
+    })
+    .unwrap();
+
+    // `Client::post` decodes to a `Value`, which is then re-shaped into the typed response
+    let post = || async {
+        let value = sdk
+            .client()
+            .post("/pse", &PseParams::default())
+            .await
+            .unwrap();
+        serde_json::from_value::<PseResponse>(value).unwrap()
+    };
+    let typed = || async { sdk.pse(PseParams::default()).await.unwrap() };
+    runtime.block_on(async {
+        assert_eq!(post().await.peoples.len(), PEOPLE);
+        assert_eq!(typed().await.peoples.len(), PEOPLE);
+    });
+
+    println!("through the client, including the HTTP round trip:");
+    report("post", || {
+        runtime.block_on(async {
+            for _ in 0..ITERATIONS {
+                black_box(post().await);
+            }
+        })
+    });
+    report("pse", || {
+        runtime.block_on(async {
+            for _ in 0..ITERATIONS {
+                black_box(typed().await);
+            }
+        })
+    });
+}
@@ -0,0 +1,49 @@
+//! Check the hash chain of an audit log and its rotated files
+//!
+//! ```sh
+//! cargo run --example audit_verify --features audit -- audit.jsonl
+//! ```
+
+use cufinder_rust::audit::AuditLog;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let Some(path) = std::env::args().nth(1) else {
+        eprintln!("usage: audit_verify <audit.jsonl>");
+        return ExitCode::from(2);
+    };
+
+    let verification = match AuditLog::verify(&path) {
+        Ok(verification) => verification,
+        Err(e) => {
+            eprintln!("cannot read {}: {}", path, e);
+            return ExitCode::from(2);
+        }
+    };
+    if verification.files.is_empty() {
+        eprintln!("no audit log at {}", path);
+        return ExitCode::from(2);
+    }
+    for problem in &verification.problems {
+        println!("{}", problem);
+    }
+    println!(
+        "{} records in {} files, last hash {}",
+        verification.records,
+        verification.files.len(),
+        verification.last_hash.as_deref().unwrap_or("-")
+    );
+    if !verification.anchored && verification.records > 0 {
+        println!(
+            "oldest records were rotated away; the chain is checked from the oldest kept file"
+        );
+    }
+
+    if verification.is_intact() {
+        println!("intact");
+        ExitCode::SUCCESS
+    } else {
+        println!("{} problems found", verification.problems.len());
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,228 @@
+//! The [`CufinderApi`] trait over every endpoint, and [`FakeCufinder`] for tests
+//!
+//! Code that takes `&impl CufinderApi` instead of `&CufinderSDK` can be unit tested
+//! against a [`FakeCufinder`] with scripted responses and no HTTP server.
+
+use crate::{
+    error::{CufinderError, Result},
+    sdk::CufinderSDK,
+    types::*,
+};
+use serde_json::{Value, json};
+use std::{
+    future::Future,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+/// A call made to a [`FakeCufinder`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FakeCall {
+    /// SDK method name, such as `"enc"`
+    pub method: &'static str,
+    /// Arguments by parameter name
+    pub args: Value,
+}
+
+macro_rules! cufinder_api {
+    ($(
+        $(#[doc = $doc:literal])*
+        $method:ident / $on_method:ident ($($arg:ident: $ty:ty),*) -> $response:ty;
+    )*) => {
+        /// Every CUFinder endpoint, implemented by [`CufinderSDK`] and [`FakeCufinder`]
+        pub trait CufinderApi: Send + Sync {
+            $(
+                $(#[doc = $doc])*
+                fn $method(&self, $($arg: $ty),*) -> impl Future<Output = Result<$response>> + Send;
+            )*
+        }
+
+        impl CufinderApi for CufinderSDK {
+            $(
+                fn $method(&self, $($arg: $ty),*) -> impl Future<Output = Result<$response>> + Send {
+                    CufinderSDK::$method(self, $($arg),*)
+                }
+            )*
+        }
+
+        #[derive(Default)]
+        #[allow(clippy::type_complexity)]
+        struct Handlers {
+            $($method: Option<Arc<dyn Fn($($ty),*) -> Result<$response> + Send + Sync>>,)*
+        }
+
+        impl FakeCufinder {
+            $(
+                #[doc = concat!("Answer `", stringify!($method), "` calls with `handler`")]
+                pub fn $on_method(
+                    &self,
+                    handler: impl Fn($($ty),*) -> Result<$response> + Send + Sync + 'static,
+                ) -> &Self {
+                    self.handlers().$method = Some(Arc::new(handler));
+                    self
+                }
+            )*
+        }
+
+        impl CufinderApi for FakeCufinder {
+            $(
+                async fn $method(&self, $($arg: $ty),*) -> Result<$response> {
+                    self.record(stringify!($method), json!({ $(stringify!($arg): &$arg),* }));
+                    let handler = self.handlers().$method.clone();
+                    match handler {
+                        Some(handler) => handler($($arg),*),
+                        None => Err(unscripted(stringify!($method))),
+                    }
+                }
+            )*
+        }
+    };
+}
+
+cufinder_api! {
+    /// CUF - Get company domain from company name
+    cuf / on_cuf(company_name: &str, country_code: &str) -> CufResponse;
+    /// LCUF - Get LinkedIn URL from company name
+    lcuf / on_lcuf(company_name: &str) -> LcufResponse;
+    /// DTC - Get company name from domain
+    dtc / on_dtc(company_website: &str) -> DtcResponse;
+    /// DTE - Get company emails from domain
+    dte / on_dte(company_website: &str) -> DteResponse;
+    /// NTP - Get company phones from company name
+    ntp / on_ntp(company_name: &str) -> NtpResponse;
+    /// EPP - Enrich LinkedIn profile
+    epp / on_epp(linkedin_url: &str) -> EppResponse;
+    /// REL - Reverse email lookup
+    rel / on_rel(email: &str) -> RelResponse;
+    /// FWE - Get email from profile
+    fwe / on_fwe(linkedin_url: &str) -> FweResponse;
+    /// TEP - Enrich person information
+    tep / on_tep(full_name: &str, company: &str) -> TepResponse;
+    /// FCL - Get company lookalikes
+    fcl / on_fcl(query: &str) -> FclResponse;
+    /// ELF - Get company fundraising information
+    elf / on_elf(query: &str) -> ElfResponse;
+    /// CAR - Get company revenue
+    car / on_car(query: &str) -> CarResponse;
+    /// FCC - Get company subsidiaries
+    fcc / on_fcc(query: &str) -> FccResponse;
+    /// FTS - Get company tech stack
+    fts / on_fts(query: &str) -> FtsResponse;
+    /// ENC - Enrich company information
+    enc / on_enc(query: &str) -> EncResponse;
+    /// CEC - Get company employee countries
+    cec / on_cec(query: &str) -> CecResponse;
+    /// CLO - Get company locations
+    clo / on_clo(query: &str) -> CloResponse;
+    /// CSE - Search companies
+    cse / on_cse(params: CseParams) -> CseResponse;
+    /// PSE - Search people
+    pse / on_pse(params: PseParams) -> PseResponse;
+    /// LBS - Search local businesses
+    lbs / on_lbs(params: LbsParams) -> LbsResponse;
+    /// BCD - B2B Customers Finder
+    bcd / on_bcd(url: &str) -> BcdResponse;
+    /// CCP - Company Career Page Finder
+    ccp / on_ccp(url: &str) -> CcpResponse;
+    /// ISC - Company Saas Checker
+    isc / on_isc(url: &str) -> IscResponse;
+    /// CBC - Company B2B or B2C Checker
+    cbc / on_cbc(url: &str) -> CbcResponse;
+    /// CSC - Company Mission Statement
+    csc / on_csc(url: &str) -> CscResponse;
+    /// CSN - Company Snapshot
+    csn / on_csn(url: &str) -> CsnResponse;
+    /// NAO - Phone Number Normalizer
+    nao / on_nao(phone: &str) -> NaoResponse;
+    /// NAA - Address Normalizer
+    naa / on_naa(address: &str) -> NaaResponse;
+    /// CEF - Company Employee Finder
+    cef / on_cef(query: &str, page: Option<i32>) -> CefResponse;
+    /// NAC - Normalize Company Name
+    nac / on_nac(company: &str) -> NacResponse;
+    /// CAA - Company Activity API
+    caa / on_caa(query: &str, page: Option<i32>) -> CaaResponse;
+    /// CJA - Company Jobs API
+    cja / on_cja(params: CjaParams) -> CjaResponse;
+}
+
+/// In-memory [`CufinderApi`] for tests, with scripted responses and recorded calls.
+///
+/// Methods without a scripted handler fail with [`CufinderError::UnknownError`].
+///
+/// ```
+/// use cufinder_rust::{CufinderApi, DtcResponse, api::FakeCufinder};
+///
+/// # tokio_test::block_on(async {
+/// let fake = FakeCufinder::new();
+/// fake.on_dtc(|domain| {
+///     let name = domain.trim_end_matches(".com").to_uppercase();
+///     Ok(serde_json::from_value::<DtcResponse>(serde_json::json!({ "company_name": name }))?)
+/// });
+///
+/// assert_eq!(fake.dtc("acme.com").await.unwrap().company_name, "ACME");
+/// assert_eq!(fake.calls_to("dtc"), vec![serde_json::json!({ "company_website": "acme.com" })]);
+/// # });
+/// ```
+#[derive(Default)]
+pub struct FakeCufinder {
+    handlers: Mutex<Handlers>,
+    calls: Mutex<Vec<FakeCall>>,
+}
+
+impl FakeCufinder {
+    /// A fake with no scripted responses
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every call so far, oldest first
+    pub fn calls(&self) -> Vec<FakeCall> {
+        self.lock_calls().clone()
+    }
+
+    /// Arguments of every call to `method` so far, oldest first
+    pub fn calls_to(&self, method: &str) -> Vec<Value> {
+        self.lock_calls()
+            .iter()
+            .filter(|call| call.method == method)
+            .map(|call| call.args.clone())
+            .collect()
+    }
+
+    /// Number of calls to `method` so far
+    pub fn call_count(&self, method: &str) -> usize {
+        self.lock_calls()
+            .iter()
+            .filter(|call| call.method == method)
+            .count()
+    }
+
+    /// Forget recorded calls, keeping scripted responses
+    pub fn clear_calls(&self) {
+        self.lock_calls().clear();
+    }
+
+    fn record(&self, method: &'static str, args: Value) {
+        self.lock_calls().push(FakeCall { method, args });
+    }
+
+    fn handlers(&self) -> MutexGuard<'_, Handlers> {
+        self.handlers.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn lock_calls(&self) -> MutexGuard<'_, Vec<FakeCall>> {
+        self.calls.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl std::fmt::Debug for FakeCufinder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FakeCufinder")
+            .field("calls", &self.lock_calls().len())
+            .finish()
+    }
+}
+
+fn unscripted(method: &str) -> CufinderError {
+    CufinderError::UnknownError(format!("FakeCufinder: no response scripted for {}", method))
+}
@@ -0,0 +1,548 @@
+//! Tamper-evident audit log of personal-data lookups
+//!
+//! Each call to an audited endpoint appends one JSONL [`AuditRecord`] with the caller tag,
+//! the request parameters with emails, names and LinkedIn URLs hashed or masked, and the
+//! outcome. Every record carries the SHA-256 hash of the previous one, so
+//! [`AuditLog::verify`] can detect edited, removed or reordered lines.
+//!
+//! Records are written on a blocking thread, off the async executor. A failed write is
+//! reported as [`Event::AuditWriteFailed`](crate::Event::AuditWriteFailed) and the call
+//! still succeeds, unless [`AuditConfig::strict`] makes it fail the call instead.
+
+use crate::{
+    cache_store::now_ms,
+    error::{CufinderError, ErrorKind, Result},
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard},
+};
+
+/// Endpoints that look up personal data, audited by default
+pub const PERSONAL_DATA_ENDPOINTS: [&str; 5] = ["/rel", "/epp", "/fwe", "/tep", "/pse"];
+
+/// `prev_hash` of the first record of a log
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// How a kind of personal identifier is written to the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Redaction {
+    /// Salted SHA-256 of the normalized value, so lookups of the same person can be matched
+    Hash,
+    /// Partly hidden, such as `j***@example.com`
+    Mask,
+    /// Left out of the record
+    Remove,
+    /// Written as is
+    Keep,
+}
+
+/// Which endpoints are audited, how identifiers are redacted and when the log rotates
+#[derive(Clone)]
+pub struct AuditConfig {
+    /// Audited endpoint paths, [`PERSONAL_DATA_ENDPOINTS`] by default
+    pub endpoints: BTreeSet<String>,
+    pub emails: Redaction,
+    pub names: Redaction,
+    pub linkedin_urls: Redaction,
+    salt: String,
+    /// Rotate the log before it grows past this many bytes
+    pub max_bytes: Option<u64>,
+    /// Rotated files to keep besides the current one
+    pub max_files: usize,
+    /// Fail calls whose audit record cannot be written
+    pub strict: bool,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            endpoints: PERSONAL_DATA_ENDPOINTS
+                .iter()
+                .map(|e| e.to_string())
+                .collect(),
+            emails: Redaction::Hash,
+            names: Redaction::Hash,
+            linkedin_urls: Redaction::Hash,
+            salt: String::new(),
+            max_bytes: None,
+            max_files: 10,
+            strict: false,
+        }
+    }
+}
+
+impl AuditConfig {
+    /// Audit the personal-data endpoints, hashing every identifier, without rotation.
+    ///
+    /// Hashing needs a secret [`salt`](Self::salt): without one, hashes of emails and names
+    /// can be reversed by hashing a list of candidates, so [`AuditLog::open`] rejects a
+    /// config that hashes identifiers without a salt. Keep the salt out of the log's reach.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also audit `endpoint`, such as `"/cse"`
+    pub fn endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoints.insert(endpoint.into());
+        self
+    }
+
+    /// Write emails with `redaction`
+    pub fn emails(mut self, redaction: Redaction) -> Self {
+        self.emails = redaction;
+        self
+    }
+
+    /// Write person names with `redaction`
+    pub fn names(mut self, redaction: Redaction) -> Self {
+        self.names = redaction;
+        self
+    }
+
+    /// Write LinkedIn URLs with `redaction`
+    pub fn linkedin_urls(mut self, redaction: Redaction) -> Self {
+        self.linkedin_urls = redaction;
+        self
+    }
+
+    /// Prefix hashed identifiers with a secret `salt`, so they cannot be reversed by hashing
+    /// guessed values
+    pub fn salt(mut self, salt: impl Into<String>) -> Self {
+        self.salt = salt.into();
+        self
+    }
+
+    /// Rotate the log to `<path>.1` before it grows past `bytes`, keeping `max_files`
+    /// rotated files
+    pub fn rotate(mut self, bytes: u64, max_files: usize) -> Self {
+        self.max_bytes = Some(bytes);
+        self.max_files = max_files;
+        self
+    }
+
+    /// Fail calls whose audit record cannot be written, such as on a full disk, with the
+    /// write error. The request has already been sent and charged by then.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Check that hashed identifiers are salted
+    pub fn validate(&self) -> Result<()> {
+        let hashes = [self.emails, self.names, self.linkedin_urls].contains(&Redaction::Hash);
+        if hashes && self.salt.is_empty() {
+            return Err(CufinderError::ValidationError(
+                "hashing audited identifiers requires a salt, see AuditConfig::salt".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether calls to `endpoint` are audited
+    pub fn audits(&self, endpoint: &str) -> bool {
+        self.endpoints.contains(endpoint)
+    }
+
+    /// The value of request parameter `field` as written to the log, or `None` to leave it out
+    pub fn redact(&self, field: &str, value: &str) -> Option<String> {
+        let Some((kind, redaction)) = self.classify(field) else {
+            return Some(value.to_string());
+        };
+        match redaction {
+            Redaction::Hash => Some(self.hash(value)),
+            Redaction::Mask => Some(mask(kind, value)),
+            Redaction::Remove => None,
+            Redaction::Keep => Some(value.to_string()),
+        }
+    }
+
+    fn classify(&self, field: &str) -> Option<(Identifier, Redaction)> {
+        if field.contains("email") {
+            Some((Identifier::Email, self.emails))
+        } else if field.contains("linkedin") {
+            Some((Identifier::LinkedIn, self.linkedin_urls))
+        } else if matches!(field, "full_name" | "first_name" | "last_name") {
+            Some((Identifier::Name, self.names))
+        } else {
+            None
+        }
+    }
+
+    fn hash(&self, value: &str) -> String {
+        let normalized = value.trim().to_lowercase();
+        format!(
+            "sha256:{}",
+            sha256_hex(&[self.salt.as_bytes(), normalized.as_bytes()])
+        )
+    }
+}
+
+impl fmt::Debug for AuditConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuditConfig")
+            .field("endpoints", &self.endpoints)
+            .field("emails", &self.emails)
+            .field("names", &self.names)
+            .field("linkedin_urls", &self.linkedin_urls)
+            .field("salt", &(!self.salt.is_empty()).then_some("****"))
+            .field("max_bytes", &self.max_bytes)
+            .field("max_files", &self.max_files)
+            .field("strict", &self.strict)
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Identifier {
+    Email,
+    Name,
+    LinkedIn,
+}
+
+fn mask(kind: Identifier, value: &str) -> String {
+    let initial = |word: &str| {
+        word.chars()
+            .next()
+            .map(|c| format!("{}***", c))
+            .unwrap_or_default()
+    };
+    match kind {
+        Identifier::Email => match value.split_once('@') {
+            Some((local, domain)) => format!("{}@{}", initial(local), domain),
+            None => "***".to_string(),
+        },
+        Identifier::Name => value
+            .split_whitespace()
+            .map(initial)
+            .collect::<Vec<_>>()
+            .join(" "),
+        Identifier::LinkedIn => match value.find("/in/") {
+            Some(at) => format!("{}***", &value[..at + 4]),
+            None => "***".to_string(),
+        },
+    }
+}
+
+fn sha256_hex(parts: &[&[u8]]) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Result of an audited call
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum AuditOutcome {
+    Success,
+    Error { kind: ErrorKind },
+}
+
+/// One audited call, as written to the log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// Position in the log, starting at 1 and continuing across rotations
+    pub seq: u64,
+    /// Unix time of the call, in milliseconds
+    pub timestamp: u64,
+    pub endpoint: String,
+    /// Tag of the caller, set with [`CufinderSDK::tagged`](crate::CufinderSDK::tagged)
+    pub tag: Option<String>,
+    /// Request parameters, with identifiers redacted
+    pub params: BTreeMap<String, String>,
+    pub outcome: AuditOutcome,
+    pub cache_hit: bool,
+    /// Hash of the previous record, or [`GENESIS_HASH`]
+    pub prev_hash: String,
+    /// SHA-256 of `prev_hash` and this record without its `hash`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub hash: String,
+}
+
+impl AuditRecord {
+    /// The hash this record should carry
+    pub fn compute_hash(&self) -> Result<String> {
+        let unhashed = Self {
+            hash: String::new(),
+            ..self.clone()
+        };
+        let body = serde_json::to_string(&unhashed)?;
+        Ok(sha256_hex(&[self.prev_hash.as_bytes(), body.as_bytes()]))
+    }
+}
+
+struct Writer {
+    file: File,
+    size: u64,
+    seq: u64,
+    last_hash: String,
+}
+
+/// Appends hash-chained [`AuditRecord`]s to a JSONL file, rotating it by size
+pub struct AuditLog {
+    path: PathBuf,
+    config: AuditConfig,
+    writer: Mutex<Writer>,
+}
+
+impl AuditLog {
+    /// Open `path` for appending, creating it if needed and continuing its hash chain.
+    ///
+    /// Fails with [`CufinderError::ValidationError`] if `config` hashes identifiers
+    /// without a salt.
+    pub fn open(path: impl AsRef<Path>, config: AuditConfig) -> Result<Self> {
+        config.validate()?;
+        let path = path.as_ref().to_path_buf();
+        let last = match last_record(&path)? {
+            Some(record) => Some(record),
+            None => last_record(&rotated(&path, 1))?,
+        };
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let mut size = file.metadata()?.len();
+        // A crash mid-write leaves a truncated last line; start the next record on its own line
+        if size > 0 && !ends_with_newline(&path)? {
+            file.write_all(b"\n")?;
+            size += 1;
+        }
+        let (seq, last_hash) = match last {
+            Some(record) => (record.seq, record.hash),
+            None => (0, GENESIS_HASH.to_string()),
+        };
+        Ok(Self {
+            path,
+            config,
+            writer: Mutex::new(Writer {
+                file,
+                size,
+                seq,
+                last_hash,
+            }),
+        })
+    }
+
+    /// Path of the current log file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The configuration in use
+    pub fn config(&self) -> &AuditConfig {
+        &self.config
+    }
+
+    /// Append a record of a call to `endpoint` with form-encoded `params`, if it is audited
+    pub(crate) fn record(
+        &self,
+        endpoint: &str,
+        tag: Option<&str>,
+        params: &str,
+        outcome: AuditOutcome,
+        cache_hit: bool,
+    ) -> Result<()> {
+        if !self.config.audits(endpoint) {
+            return Ok(());
+        }
+        let pairs: Vec<(String, String)> = serde_urlencoded::from_str(params).unwrap_or_default();
+        let params = pairs
+            .iter()
+            .filter_map(|(field, value)| Some((field.clone(), self.config.redact(field, value)?)))
+            .collect();
+
+        let mut writer = self.lock();
+        let mut record = AuditRecord {
+            seq: writer.seq + 1,
+            timestamp: now_ms(),
+            endpoint: endpoint.to_string(),
+            tag: tag.map(str::to_string),
+            params,
+            outcome,
+            cache_hit,
+            prev_hash: writer.last_hash.clone(),
+            hash: String::new(),
+        };
+        record.hash = record.compute_hash()?;
+        let line = format!("{}\n", serde_json::to_string(&record)?);
+
+        if let Some(max_bytes) = self.config.max_bytes
+            && writer.size > 0
+            && writer.size + line.len() as u64 > max_bytes
+        {
+            self.rotate(&mut writer)?;
+        }
+        writer.file.write_all(line.as_bytes())?;
+        writer.file.flush()?;
+        writer.size += line.len() as u64;
+        writer.seq = record.seq;
+        writer.last_hash = record.hash;
+        Ok(())
+    }
+
+    /// Shift `<path>.N` to `<path>.N+1`, dropping the oldest, and start a new current file
+    fn rotate(&self, writer: &mut Writer) -> Result<()> {
+        if self.config.max_files == 0 {
+            fs::remove_file(&self.path)?;
+        } else {
+            let _ = fs::remove_file(rotated(&self.path, self.config.max_files));
+            for n in (1..self.config.max_files).rev() {
+                let from = rotated(&self.path, n);
+                if from.exists() {
+                    fs::rename(&from, rotated(&self.path, n + 1))?;
+                }
+            }
+            fs::rename(&self.path, rotated(&self.path, 1))?;
+        }
+        writer.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writer.size = 0;
+        Ok(())
+    }
+
+    /// Check the hash chain of the log at `path` and its rotated files, oldest first
+    pub fn verify(path: impl AsRef<Path>) -> Result<AuditVerification> {
+        let path = path.as_ref();
+        let mut files: Vec<PathBuf> = (1..)
+            .map(|n| rotated(path, n))
+            .take_while(|file| file.exists())
+            .collect();
+        files.reverse();
+        if path.exists() {
+            files.push(path.to_path_buf());
+        }
+
+        let mut verification = AuditVerification {
+            files: files.clone(),
+            ..AuditVerification::default()
+        };
+        let mut expected: Option<(u64, String)> = None;
+        for file in &files {
+            for (index, line) in BufReader::new(File::open(file)?).lines().enumerate() {
+                let line = line?;
+                let mut problem = |reason: String| {
+                    verification.problems.push(AuditProblem {
+                        file: file.clone(),
+                        line: index + 1,
+                        reason,
+                    })
+                };
+                let record: AuditRecord = match serde_json::from_str(&line) {
+                    Ok(record) => record,
+                    Err(e) => {
+                        problem(format!("unreadable record: {}", e));
+                        continue;
+                    }
+                };
+                match &expected {
+                    Some((seq, hash)) => {
+                        if record.seq != seq + 1 {
+                            problem(format!("expected seq {}, found {}", seq + 1, record.seq));
+                        }
+                        if &record.prev_hash != hash {
+                            problem("prev_hash does not match the previous record".to_string());
+                        }
+                    }
+                    None => {
+                        verification.anchored = record.seq == 1 && record.prev_hash == GENESIS_HASH;
+                    }
+                }
+                if record.compute_hash()? != record.hash {
+                    problem("hash does not match the record".to_string());
+                }
+                verification.records += 1;
+                expected = Some((record.seq, record.hash));
+            }
+        }
+        verification.last_hash = expected.map(|(_, hash)| hash);
+        Ok(verification)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Writer> {
+        self.writer.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl fmt::Debug for AuditLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuditLog")
+            .field("path", &self.path)
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+/// A line of the audit log that failed verification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditProblem {
+    pub file: PathBuf,
+    /// Line number, starting at 1
+    pub line: usize,
+    pub reason: String,
+}
+
+impl fmt::Display for AuditProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.file.display(), self.line, self.reason)
+    }
+}
+
+/// Outcome of [`AuditLog::verify`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditVerification {
+    /// Files checked, oldest first
+    pub files: Vec<PathBuf>,
+    pub records: u64,
+    /// Whether the oldest record starts the chain. False once rotation has dropped old files.
+    pub anchored: bool,
+    /// Hash of the newest record, to keep elsewhere as proof the log was not truncated
+    pub last_hash: Option<String>,
+    pub problems: Vec<AuditProblem>,
+}
+
+impl AuditVerification {
+    /// Whether every record is readable and chained to the one before it
+    pub fn is_intact(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+fn ends_with_newline(path: &Path) -> Result<bool> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::End(-1))?;
+    let mut last = [0u8];
+    file.read_exact(&mut last)?;
+    Ok(last[0] == b'\n')
+}
+
+fn rotated(path: &Path, n: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(format!(".{}", n));
+    PathBuf::from(name)
+}
+
+/// The last readable record of a log file, if it exists
+fn last_record(path: &Path) -> Result<Option<AuditRecord>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let mut last = None;
+    for line in BufReader::new(File::open(path)?).lines() {
+        if let Ok(record) = serde_json::from_str(&line?) {
+            last = Some(record);
+        }
+    }
+    Ok(last)
+}
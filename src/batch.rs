@@ -0,0 +1,195 @@
+//! Bounded-concurrency batch execution for enrichment calls
+
+use crate::{
+    error::{ErrorKind, Result},
+    events::{Event, EventSink},
+    types::ApiResponse,
+};
+use futures::{Stream, StreamExt, future::Either, stream};
+use std::{collections::BTreeMap, future::Future};
+
+/// Settings for [`CufinderSDK::batch`](crate::CufinderSDK::batch)
+#[derive(Debug, Clone)]
+pub struct BatchOptions {
+    /// Maximum number of requests in flight at once
+    pub concurrency: usize,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self { concurrency: 8 }
+    }
+}
+
+impl BatchOptions {
+    /// Default options, running up to 8 requests at once
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run up to `concurrency` requests at once
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+}
+
+/// Settings for [`CufinderSDK::enrich_stream`](crate::CufinderSDK::enrich_stream)
+#[derive(Debug, Clone)]
+pub struct EnrichOptions {
+    /// Maximum number of requests in flight at once
+    pub concurrency: usize,
+    /// Emit results in input order rather than as soon as each completes
+    pub ordered: bool,
+}
+
+impl Default for EnrichOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 8,
+            ordered: false,
+        }
+    }
+}
+
+impl EnrichOptions {
+    /// Default options, running up to 8 requests at once and emitting results as they complete
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run up to `concurrency` requests at once
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Emit results in input order, holding back results that complete early
+    pub fn ordered(mut self, ordered: bool) -> Self {
+        self.ordered = ordered;
+        self
+    }
+}
+
+/// Aggregate outcome of a batch
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchSummary {
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub failures_by_kind: BTreeMap<ErrorKind, usize>,
+    /// Sum of `credit_count` over successful responses
+    pub credits_used: i64,
+}
+
+impl BatchSummary {
+    /// Add one result to the summary
+    pub fn record<R: ApiResponse>(&mut self, result: &Result<R>) {
+        self.total += 1;
+        match result {
+            Ok(response) => {
+                self.succeeded += 1;
+                self.credits_used += i64::from(response.credit_count());
+            }
+            Err(error) => {
+                self.failed += 1;
+                *self.failures_by_kind.entry(error.kind()).or_default() += 1;
+            }
+        }
+    }
+}
+
+/// Per-item results of a batch, in input order, with a summary
+#[derive(Debug)]
+pub struct BatchOutput<R> {
+    pub results: Vec<Result<R>>,
+    pub summary: BatchSummary,
+}
+
+/// Run `call` over `inputs` with at most `options.concurrency` calls in flight,
+/// keeping results in input order and reporting each item to `events` as it completes
+pub(crate) async fn run<I, F, Fut, R>(
+    inputs: I,
+    options: &BatchOptions,
+    events: &EventSink,
+    call: F,
+) -> BatchOutput<R>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> Fut,
+    Fut: Future<Output = Result<R>>,
+    R: ApiResponse,
+{
+    let mut call = call;
+    let results: Vec<Result<R>> = stream::iter(inputs)
+        .enumerate()
+        .map(|(index, input)| {
+            let future = call(input);
+            async move {
+                let result = future.await;
+                emit_item(events, index, &result);
+                result
+            }
+        })
+        .buffered(options.concurrency.max(1))
+        .collect()
+        .await;
+
+    let mut summary = BatchSummary::default();
+    for result in &results {
+        summary.record(result);
+    }
+
+    BatchOutput { results, summary }
+}
+
+/// Run `call` over each record of `input` with at most `options.concurrency` calls in
+/// flight, pairing each result with its record.
+///
+/// Input is only pulled while there is spare capacity, and dropping the returned stream
+/// drops every in-flight call.
+pub(crate) fn enrich<S, F, Fut, R>(
+    input: S,
+    options: EnrichOptions,
+    events: EventSink,
+    call: F,
+) -> impl Stream<Item = (S::Item, Result<R>)>
+where
+    S: Stream,
+    S::Item: Clone,
+    F: FnMut(S::Item) -> Fut,
+    Fut: Future<Output = Result<R>>,
+    R: ApiResponse,
+{
+    let mut call = call;
+    let calls = input.enumerate().map(move |(index, record)| {
+        let future = call(record.clone());
+        let events = events.clone();
+        async move {
+            let result = future.await;
+            emit_item(&events, index, &result);
+            (record, result)
+        }
+    });
+
+    let concurrency = options.concurrency.max(1);
+    if options.ordered {
+        Either::Left(calls.buffered(concurrency))
+    } else {
+        Either::Right(calls.buffer_unordered(concurrency))
+    }
+}
+
+/// Report a finished batch item to `events`, as soon as it completes
+fn emit_item<R: ApiResponse>(events: &EventSink, index: usize, result: &Result<R>) {
+    match result {
+        Ok(response) => events.emit(|| Event::ItemSucceeded {
+            index,
+            credits: response.credit_count(),
+        }),
+        Err(error) => events.emit(|| Event::ItemFailed {
+            index,
+            error: error.kind(),
+        }),
+    }
+}
@@ -0,0 +1,339 @@
+//! Fluent builders for search parameters
+
+use crate::{
+    error::{CufinderError, Result},
+    types::{CjaParams, CseParams, LbsParams, PseParams},
+};
+
+fn check_text(field: &str, value: &Option<String>) -> Result<()> {
+    match value {
+        Some(v) if v.trim().is_empty() => Err(CufinderError::ValidationError(format!("{} must not be empty", field))),
+        _ => Ok(()),
+    }
+}
+
+fn check_list(field: &str, values: &Option<Vec<String>>) -> Result<()> {
+    match values {
+        Some(v) if v.is_empty() || v.iter().any(|item| item.trim().is_empty()) => Err(
+            CufinderError::ValidationError(format!("{} must not be empty or contain empty values", field)),
+        ),
+        _ => Ok(()),
+    }
+}
+
+fn check_range(min_field: &str, min: Option<i32>, max_field: &str, max: Option<i32>) -> Result<()> {
+    for (field, value) in [(min_field, min), (max_field, max)] {
+        if value.is_some_and(|v| v < 0) {
+            return Err(CufinderError::ValidationError(format!("{} must not be negative", field)));
+        }
+    }
+    match (min, max) {
+        (Some(min), Some(max)) if min > max => Err(CufinderError::ValidationError(format!(
+            "{} ({}) must not be greater than {} ({})",
+            min_field, min, max_field, max
+        ))),
+        _ => Ok(()),
+    }
+}
+
+fn check_page(page: Option<i32>) -> Result<()> {
+    match page {
+        Some(p) if p < 1 => Err(CufinderError::ValidationError("page must be 1 or greater".to_string())),
+        _ => Ok(()),
+    }
+}
+
+/// Setters for optional string fields
+macro_rules! text_setters {
+    ($($field:ident),* $(,)?) => {
+        $(
+            #[doc = concat!("Set `", stringify!($field), "`")]
+            pub fn $field(mut self, value: impl Into<String>) -> Self {
+                self.params.$field = Some(value.into());
+                self
+            }
+        )*
+    };
+}
+
+/// Setters for an integer range: `<min>`, `<max>` and `<between>`
+macro_rules! range_setters {
+    ($between:ident, $min:ident, $max:ident) => {
+        #[doc = concat!("Set `", stringify!($min), "`")]
+        pub fn $min(mut self, value: i32) -> Self {
+            self.params.$min = Some(value);
+            self
+        }
+
+        #[doc = concat!("Set `", stringify!($max), "`")]
+        pub fn $max(mut self, value: i32) -> Self {
+            self.params.$max = Some(value);
+            self
+        }
+
+        #[doc = concat!("Set both `", stringify!($min), "` and `", stringify!($max), "`")]
+        pub fn $between(self, min: i32, max: i32) -> Self {
+            self.$min(min).$max(max)
+        }
+    };
+}
+
+/// Setter for a list of strings
+macro_rules! list_setter {
+    ($field:ident) => {
+        #[doc = concat!("Set `", stringify!($field), "`")]
+        pub fn $field<I, S>(mut self, values: I) -> Self
+        where
+            I: IntoIterator<Item = S>,
+            S: Into<String>,
+        {
+            self.params.$field = Some(values.into_iter().map(Into::into).collect());
+            self
+        }
+    };
+}
+
+/// Setter for the page number
+macro_rules! page_setter {
+    () => {
+        /// Set the page to fetch, starting at 1
+        pub fn page(mut self, page: i32) -> Self {
+            self.params.page = Some(page);
+            self
+        }
+    };
+}
+
+impl CseParams {
+    /// Start building company search parameters
+    pub fn builder() -> CseParamsBuilder {
+        CseParamsBuilder::default()
+    }
+
+    /// Check for empty strings, inverted ranges and invalid pages
+    pub fn validate(&self) -> Result<()> {
+        for (field, value) in [
+            ("name", &self.name),
+            ("country", &self.country),
+            ("state", &self.state),
+            ("city", &self.city),
+            ("industry", &self.industry),
+            ("employee_size", &self.employee_size),
+        ] {
+            check_text(field, value)?;
+        }
+        check_list("products_services", &self.products_services)?;
+        check_range("followers_count_min", self.followers_count_min, "followers_count_max", self.followers_count_max)?;
+        check_range("founded_after_year", self.founded_after_year, "founded_before_year", self.founded_before_year)?;
+        check_range("funding_amount_min", self.funding_amount_min, "funding_amount_max", self.funding_amount_max)?;
+        check_range("annual_revenue_min", self.annual_revenue_min, "annual_revenue_max", self.annual_revenue_max)?;
+        check_page(self.page)
+    }
+}
+
+/// Builder for [`CseParams`]
+#[derive(Debug, Clone, Default)]
+pub struct CseParamsBuilder {
+    params: CseParams,
+}
+
+impl CseParamsBuilder {
+    text_setters!(name, country, state, city, industry);
+    list_setter!(products_services);
+    range_setters!(followers_count_between, followers_count_min, followers_count_max);
+    range_setters!(founded_between, founded_after_year, founded_before_year);
+    range_setters!(funding_amount_between, funding_amount_min, funding_amount_max);
+    range_setters!(annual_revenue_between, annual_revenue_min, annual_revenue_max);
+    page_setter!();
+
+    /// Set `employee_size` from an [`EmployeeSize`](crate::EmployeeSize) or a raw range such as `"51-200"`
+    pub fn employee_size(mut self, size: impl AsRef<str>) -> Self {
+        self.params.employee_size = Some(size.as_ref().to_string());
+        self
+    }
+
+    /// Set `is_school`
+    pub fn is_school(mut self, is_school: bool) -> Self {
+        self.params.is_school = Some(is_school);
+        self
+    }
+
+    /// Validate and return the parameters
+    pub fn build(self) -> Result<CseParams> {
+        self.params.validate()?;
+        Ok(self.params)
+    }
+}
+
+impl PseParams {
+    /// Start building person search parameters
+    pub fn builder() -> PseParamsBuilder {
+        PseParamsBuilder::default()
+    }
+
+    /// Check for empty strings, inverted ranges and invalid pages
+    pub fn validate(&self) -> Result<()> {
+        for (field, value) in [
+            ("full_name", &self.full_name),
+            ("country", &self.country),
+            ("state", &self.state),
+            ("city", &self.city),
+            ("job_title_role", &self.job_title_role),
+            ("job_title_level", &self.job_title_level),
+            ("company_country", &self.company_country),
+            ("company_state", &self.company_state),
+            ("company_city", &self.company_city),
+            ("company_name", &self.company_name),
+            ("company_linkedin_url", &self.company_linkedin_url),
+            ("company_industry", &self.company_industry),
+            ("company_employee_size", &self.company_employee_size),
+        ] {
+            check_text(field, value)?;
+        }
+        check_list("company_products_services", &self.company_products_services)?;
+        check_range(
+            "company_annual_revenue_min",
+            self.company_annual_revenue_min,
+            "company_annual_revenue_max",
+            self.company_annual_revenue_max,
+        )?;
+        check_page(self.page)
+    }
+}
+
+/// Builder for [`PseParams`]
+#[derive(Debug, Clone, Default)]
+pub struct PseParamsBuilder {
+    params: PseParams,
+}
+
+impl PseParamsBuilder {
+    text_setters!(
+        full_name,
+        country,
+        state,
+        city,
+        job_title_role,
+        job_title_level,
+        company_country,
+        company_state,
+        company_city,
+        company_name,
+        company_linkedin_url,
+        company_industry,
+    );
+    list_setter!(company_products_services);
+    range_setters!(company_annual_revenue_between, company_annual_revenue_min, company_annual_revenue_max);
+    page_setter!();
+
+    /// Set `company_employee_size` from an [`EmployeeSize`](crate::EmployeeSize) or a raw range such as `"51-200"`
+    pub fn company_employee_size(mut self, size: impl AsRef<str>) -> Self {
+        self.params.company_employee_size = Some(size.as_ref().to_string());
+        self
+    }
+
+    /// Validate and return the parameters
+    pub fn build(self) -> Result<PseParams> {
+        self.params.validate()?;
+        Ok(self.params)
+    }
+}
+
+impl LbsParams {
+    /// Start building local business search parameters
+    pub fn builder() -> LbsParamsBuilder {
+        LbsParamsBuilder::default()
+    }
+
+    /// Check for empty strings and invalid pages
+    pub fn validate(&self) -> Result<()> {
+        for (field, value) in [
+            ("name", &self.name),
+            ("country", &self.country),
+            ("state", &self.state),
+            ("city", &self.city),
+            ("industry", &self.industry),
+        ] {
+            check_text(field, value)?;
+        }
+        check_page(self.page)
+    }
+}
+
+/// Builder for [`LbsParams`]
+#[derive(Debug, Clone, Default)]
+pub struct LbsParamsBuilder {
+    params: LbsParams,
+}
+
+impl LbsParamsBuilder {
+    text_setters!(name, country, state, city, industry);
+    page_setter!();
+
+    /// Validate and return the parameters
+    pub fn build(self) -> Result<LbsParams> {
+        self.params.validate()?;
+        Ok(self.params)
+    }
+}
+
+impl CjaParams {
+    /// Start building company jobs parameters
+    pub fn builder() -> CjaParamsBuilder {
+        CjaParamsBuilder::default()
+    }
+
+    /// Check for empty strings, inverted ranges and invalid pages
+    pub fn validate(&self) -> Result<()> {
+        for (field, value) in [
+            ("name", &self.name),
+            ("country", &self.country),
+            ("state", &self.state),
+            ("city", &self.city),
+            ("industry", &self.industry),
+            ("employee_size", &self.employee_size),
+        ] {
+            check_text(field, value)?;
+        }
+        check_list("products_services", &self.products_services)?;
+        check_range("followers_count_min", self.followers_count_min, "followers_count_max", self.followers_count_max)?;
+        check_range("founded_after_year", self.founded_after_year, "founded_before_year", self.founded_before_year)?;
+        check_range("funding_amount_min", self.funding_amount_min, "funding_amount_max", self.funding_amount_max)?;
+        check_range("annual_revenue_min", self.annual_revenue_min, "annual_revenue_max", self.annual_revenue_max)?;
+        check_page(self.page)
+    }
+}
+
+/// Builder for [`CjaParams`]
+#[derive(Debug, Clone, Default)]
+pub struct CjaParamsBuilder {
+    params: CjaParams,
+}
+
+impl CjaParamsBuilder {
+    text_setters!(name, country, state, city, industry);
+    list_setter!(products_services);
+    range_setters!(followers_count_between, followers_count_min, followers_count_max);
+    range_setters!(founded_between, founded_after_year, founded_before_year);
+    range_setters!(funding_amount_between, funding_amount_min, funding_amount_max);
+    range_setters!(annual_revenue_between, annual_revenue_min, annual_revenue_max);
+    page_setter!();
+
+    /// Set `employee_size` from an [`EmployeeSize`](crate::EmployeeSize) or a raw range such as `"51-200"`
+    pub fn employee_size(mut self, size: impl AsRef<str>) -> Self {
+        self.params.employee_size = Some(size.as_ref().to_string());
+        self
+    }
+
+    /// Set `is_school`
+    pub fn is_school(mut self, is_school: bool) -> Self {
+        self.params.is_school = Some(is_school);
+        self
+    }
+
+    /// Validate and return the parameters
+    pub fn build(self) -> Result<CjaParams> {
+        self.params.validate()?;
+        Ok(self.params)
+    }
+}
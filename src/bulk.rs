@@ -0,0 +1,377 @@
+//! Resumable bulk jobs with on-disk checkpoints
+//!
+//! A [`BulkJob`] appends one JSON line per attempt to a checkpoint file. When the job
+//! is reopened, inputs that already completed are skipped and inputs that failed with a
+//! retryable error are retried until they reach the attempt limit, so a crashed or killed
+//! run does not spend credits twice.
+
+use crate::{
+    batch::BatchSummary,
+    client::{count_sends, retry_delay},
+    error::{CufinderError, ErrorKind, Result},
+    sdk::CufinderSDK,
+    types::ApiResponse,
+};
+use futures::{StreamExt, stream};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use std::{
+    collections::{HashMap, HashSet},
+    fs::{File, OpenOptions},
+    future::Future,
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Outcome of the latest attempt for an input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckpointStatus {
+    Completed,
+    Failed,
+}
+
+/// One line of the checkpoint file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointRecord {
+    /// Input key, the input itself if it is a string or its JSON encoding otherwise
+    pub key: String,
+    pub status: CheckpointStatus,
+    /// HTTP requests sent so far, across runs, including the client's own retries.
+    /// Failures before sending, such as validation errors or an exhausted budget, and
+    /// responses served from the cache are not counted.
+    pub attempts: u32,
+    /// Serialized response, for completed inputs
+    pub result: Option<Value>,
+    /// Error message and kind, for failed inputs
+    pub error: Option<String>,
+    pub error_kind: Option<String>,
+    /// Whether the failure cannot be fixed by retrying, such as a not-found result
+    #[serde(default)]
+    pub permanent: bool,
+    /// Unix timestamp of the attempt, in seconds
+    pub timestamp: u64,
+}
+
+/// Counts of inputs recorded in a checkpoint
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BulkProgress {
+    pub completed: usize,
+    /// Failed inputs that will be retried
+    pub retryable: usize,
+    /// Failed inputs that reached the attempt limit or failed permanently
+    pub exhausted: usize,
+}
+
+/// Outcome of one [`BulkJob::run`]
+#[derive(Debug, Clone, Default)]
+pub struct BulkReport {
+    /// Inputs skipped because they completed or were exhausted earlier
+    pub skipped: usize,
+    /// Attempts made during this run
+    pub summary: BatchSummary,
+}
+
+/// A bulk job backed by a JSONL checkpoint file
+#[derive(Debug)]
+pub struct BulkJob {
+    path: PathBuf,
+    file: Arc<Mutex<File>>,
+    records: HashMap<String, CheckpointRecord>,
+    max_attempts: u32,
+    concurrency: usize,
+}
+
+impl BulkJob {
+    /// Open or create the checkpoint at `path`, loading any previous progress
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut records = HashMap::new();
+
+        if path.exists() {
+            for line in BufReader::new(File::open(&path)?).lines() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                // A run killed mid-write can leave a truncated last line, which is ignored
+                if let Ok(record) = serde_json::from_str::<CheckpointRecord>(&line) {
+                    records.insert(record.key.clone(), record);
+                }
+            }
+        }
+
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Self {
+            path,
+            file: Arc::new(Mutex::new(file)),
+            records,
+            max_attempts: 3,
+            concurrency: 8,
+        })
+    }
+
+    /// HTTP requests per input before giving up, across runs, counting the client's own
+    /// retries. Defaults to 3.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Maximum number of requests in flight at once. Defaults to 8.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Path of the checkpoint file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Latest record for each input
+    pub fn records(&self) -> impl Iterator<Item = &CheckpointRecord> {
+        self.records.values()
+    }
+
+    /// Latest record for `input`
+    pub fn record<T: Serialize>(&self, input: &T) -> Result<Option<&CheckpointRecord>> {
+        Ok(self.records.get(&input_key(input)?))
+    }
+
+    /// Completed, retryable and exhausted input counts
+    pub fn progress(&self) -> BulkProgress {
+        let mut progress = BulkProgress::default();
+        for record in self.records.values() {
+            match record.status {
+                CheckpointStatus::Completed => progress.completed += 1,
+                CheckpointStatus::Failed if self.is_exhausted(record) => progress.exhausted += 1,
+                CheckpointStatus::Failed => progress.retryable += 1,
+            }
+        }
+        progress
+    }
+
+    /// Completed results deserialized as `R`, keyed by input key
+    pub fn results<R: DeserializeOwned>(&self) -> Result<HashMap<String, R>> {
+        self.records
+            .values()
+            .filter(|record| record.status == CheckpointStatus::Completed)
+            .filter_map(|record| {
+                record
+                    .result
+                    .clone()
+                    .map(|result| (record.key.clone(), result))
+            })
+            .map(|(key, result)| Ok((key, serde_json::from_value(result)?)))
+            .collect()
+    }
+
+    /// Forget every failed input, so the next run tries them again from scratch, and
+    /// rewrite the checkpoint. Returns the number of inputs reset.
+    pub fn reset_failed(&mut self) -> Result<usize> {
+        let before = self.records.len();
+        self.records
+            .retain(|_, record| record.status == CheckpointStatus::Completed);
+        let reset = before - self.records.len();
+        if reset > 0 {
+            self.compact()?;
+        }
+        Ok(reset)
+    }
+
+    /// Run `call` for every input that has not completed or been exhausted.
+    ///
+    /// Retryable failures, such as rate limiting, server errors or timeouts, are retried
+    /// with exponential backoff until they reach the attempt limit. Other failures are not
+    /// retried: errors raised before sending, such as validation errors or an exhausted
+    /// budget, are retried on the next run without using up an attempt, and errors the
+    /// API returned, such as not-found results, are permanent until
+    /// [`reset_failed`](Self::reset_failed). Each call is written to the checkpoint as
+    /// soon as it finishes. If writing the checkpoint fails, the requests already in
+    /// flight are still awaited and written before the error is returned.
+    ///
+    /// Every HTTP request counts as an attempt, including the retries the client makes on
+    /// its own (`ClientConfig::max_retries`), so a single call can use up several attempts.
+    /// Build `sdk` with `max_retries: 0` to leave retrying to the job.
+    pub async fn run<'a, I, T, F, Fut, R>(
+        &mut self,
+        sdk: &'a CufinderSDK,
+        inputs: I,
+        call: F,
+    ) -> Result<BulkReport>
+    where
+        I: IntoIterator<Item = T>,
+        T: Serialize + Clone,
+        F: Fn(&'a CufinderSDK, T) -> Fut,
+        Fut: Future<Output = Result<R>> + 'a,
+        R: ApiResponse + Serialize,
+    {
+        let mut report = BulkReport::default();
+        let mut pending = Vec::new();
+        let mut seen = HashSet::new();
+        for input in inputs {
+            let key = input_key(&input)?;
+            if !seen.insert(key.clone()) {
+                continue;
+            }
+            match self.records.get(&key) {
+                Some(record)
+                    if record.status == CheckpointStatus::Completed
+                        || self.is_exhausted(record) =>
+                {
+                    report.skipped += 1
+                }
+                _ => pending.push((key, input)),
+            }
+        }
+
+        let mut round = 0;
+        while !pending.is_empty() {
+            if round > 0 {
+                tokio::time::sleep(retry_delay(round - 1)).await;
+            }
+            round += 1;
+
+            let mut attempts = stream::iter(pending.iter().cloned())
+                .map(|(key, input)| {
+                    let call = &call;
+                    async move {
+                        let (result, sends) = count_sends(call(sdk, input)).await;
+                        (key, sends, result)
+                    }
+                })
+                .buffer_unordered(self.concurrency);
+
+            let mut retry = HashSet::new();
+            let mut write_error = None;
+            while let Some((key, sends, result)) = attempts.next().await {
+                report.summary.record(&result);
+                if result.as_ref().is_err_and(is_transient) {
+                    retry.insert(key.clone());
+                }
+                // Keep draining: the calls still in flight are paid for and must be recorded
+                if let Err(error) = self.write(key, sends, result).await {
+                    write_error.get_or_insert(error);
+                }
+            }
+            drop(attempts);
+            if let Some(error) = write_error {
+                return Err(error);
+            }
+
+            pending.retain(|(key, _)| {
+                retry.contains(key)
+                    && self
+                        .records
+                        .get(key)
+                        .is_some_and(|record| !self.is_exhausted(record))
+            });
+        }
+
+        Ok(report)
+    }
+
+    /// Rewrite the checkpoint keeping only the latest record per input
+    pub fn compact(&mut self) -> Result<()> {
+        let tmp_path = self.path.with_extension("compact.tmp");
+        {
+            let mut tmp = File::create(&tmp_path)?;
+            for record in self.records.values() {
+                writeln!(tmp, "{}", serde_json::to_string(record)?)?;
+            }
+            tmp.sync_all()?;
+        }
+        std::fs::rename(&tmp_path, &self.path)?;
+        self.file = Arc::new(Mutex::new(
+            OpenOptions::new().append(true).open(&self.path)?,
+        ));
+        Ok(())
+    }
+
+    fn is_exhausted(&self, record: &CheckpointRecord) -> bool {
+        record.status == CheckpointStatus::Failed
+            && (record.permanent || record.attempts >= self.max_attempts)
+    }
+
+    /// Append the outcome of an attempt to the checkpoint, on a blocking thread
+    async fn write<R: Serialize>(
+        &mut self,
+        key: String,
+        sends: u32,
+        result: Result<R>,
+    ) -> Result<()> {
+        let previous = self.records.get(&key).map_or(0, |r| r.attempts);
+        let sent = result.as_ref().err().is_none_or(was_sent);
+        let attempts = previous.saturating_add(sends);
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        let record = match result {
+            Ok(response) => CheckpointRecord {
+                key,
+                status: CheckpointStatus::Completed,
+                attempts,
+                result: Some(serde_json::to_value(&response)?),
+                error: None,
+                error_kind: None,
+                permanent: false,
+                timestamp,
+            },
+            Err(error) => CheckpointRecord {
+                key,
+                status: CheckpointStatus::Failed,
+                attempts,
+                result: None,
+                error_kind: Some(error.kind().to_string()),
+                permanent: sent && !is_transient(&error),
+                error: Some(error.to_string()),
+                timestamp,
+            },
+        };
+
+        let line = serde_json::to_string(&record)?;
+        let file = self.file.clone();
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let mut file = file.lock().unwrap_or_else(|e| e.into_inner());
+            writeln!(file, "{}", line)?;
+            file.flush()?;
+            Ok(())
+        })
+        .await
+        .unwrap_or_else(|e| {
+            Err(CufinderError::UnknownError(format!(
+                "checkpoint writer failed: {}",
+                e
+            )))
+        })?;
+        self.records.insert(record.key.clone(), record);
+        Ok(())
+    }
+}
+
+/// Whether an input that failed with `error` is worth another attempt. Timeouts are,
+/// unlike in the client: each one still counts against the attempt limit.
+fn is_transient(error: &CufinderError) -> bool {
+    error.is_retryable() || error.is_timeout()
+}
+
+/// Whether a request reached the API before failing with `error`
+fn was_sent(error: &CufinderError) -> bool {
+    !matches!(
+        error.kind(),
+        ErrorKind::Validation | ErrorKind::Budget | ErrorKind::DryRun | ErrorKind::CacheMiss
+    )
+}
+
+/// Stable key for an input: the string itself, or its JSON encoding
+fn input_key<T: Serialize>(input: &T) -> Result<String> {
+    match serde_json::to_value(input).map_err(CufinderError::JsonError)? {
+        Value::String(s) => Ok(s),
+        other => Ok(other.to_string()),
+    }
+}
@@ -0,0 +1,305 @@
+//! Response cache in front of [`Client::post`](crate::Client::post)
+
+use crate::{
+    cache_store::{CacheEntry, CacheStore, MemoryStore, now_ms},
+    error::{CufinderError, Result},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::Path,
+    sync::{Mutex, MutexGuard},
+    time::Duration,
+};
+
+/// Search endpoints that page through results, not cached unless enabled
+const PAGINATED_ENDPOINTS: &[&str] = &["/cse", "/pse", "/lbs", "/cef", "/caa", "/cja"];
+
+/// Settings for the response cache
+#[derive(Debug, Clone)]
+pub struct CacheConfig {
+    /// Maximum number of cached responses; the least recently used is evicted first
+    pub max_entries: usize,
+    /// How long a response stays fresh, unless overridden for its endpoint
+    pub ttl: Duration,
+    /// Per-endpoint TTL overrides, keyed by path such as `"/enc"`
+    pub endpoint_ttls: HashMap<String, Duration>,
+    /// How long a not-found (404) result is remembered, or `None` to never cache it
+    pub negative_ttl: Option<Duration>,
+    /// Cache paginated search endpoints too
+    pub cache_paginated: bool,
+    /// Serve every request from the cache, ignoring TTLs, and fail with
+    /// [`CufinderError::CacheMiss`] instead of sending it
+    pub offline: bool,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: 1000,
+            ttl: Duration::from_secs(60 * 60),
+            endpoint_ttls: HashMap::new(),
+            negative_ttl: Some(Duration::from_secs(5 * 60)),
+            cache_paginated: false,
+            offline: false,
+        }
+    }
+}
+
+impl CacheConfig {
+    /// Default settings: 1000 entries, one hour TTL, five minute negative TTL
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep at most `max_entries` responses
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// Keep responses fresh for `ttl`
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Keep responses from `endpoint` fresh for `ttl`
+    pub fn endpoint_ttl(mut self, endpoint: impl Into<String>, ttl: Duration) -> Self {
+        self.endpoint_ttls.insert(endpoint.into(), ttl);
+        self
+    }
+
+    /// Remember not-found results for `ttl`, or never when `None`
+    pub fn negative_ttl(mut self, ttl: Option<Duration>) -> Self {
+        self.negative_ttl = ttl;
+        self
+    }
+
+    /// Also cache paginated search endpoints
+    pub fn cache_paginated(mut self, cache_paginated: bool) -> Self {
+        self.cache_paginated = cache_paginated;
+        self
+    }
+
+    /// Never touch the network: serve cached responses even when stale, and fail with
+    /// [`CufinderError::CacheMiss`] for anything not cached
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// TTL for responses from `endpoint`
+    pub fn ttl_for(&self, endpoint: &str) -> Duration {
+        self.endpoint_ttls
+            .get(endpoint)
+            .copied()
+            .unwrap_or(self.ttl)
+    }
+
+    /// Whether responses from `endpoint` are cached at all. Always true offline.
+    pub fn is_cacheable(&self, endpoint: &str) -> bool {
+        self.offline || self.cache_paginated || !PAGINATED_ENDPOINTS.contains(&endpoint)
+    }
+}
+
+/// A cached outcome
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", content = "body", rename_all = "snake_case")]
+pub enum CachedResponse {
+    /// A successful response body
+    Found(Value),
+    /// A not-found error with its message
+    NotFound(String),
+}
+
+/// Hit, miss and eviction counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    /// Hits that returned a cached not-found result
+    pub negative_hits: u64,
+    pub misses: u64,
+    /// Entries dropped to stay within `max_entries`
+    pub evictions: u64,
+    /// Entries dropped because their TTL passed
+    pub expirations: u64,
+    /// Entries currently cached
+    pub entries: usize,
+}
+
+impl CacheStats {
+    /// Fraction of lookups served from the cache
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// LRU or persistent cache of API responses with per-entry expiry
+#[derive(Debug)]
+pub struct ResponseCache {
+    config: CacheConfig,
+    store: Box<dyn CacheStore>,
+    stats: Mutex<CacheStats>,
+}
+
+impl ResponseCache {
+    /// Create an empty in-memory cache holding up to `config.max_entries` responses
+    pub fn new(config: CacheConfig) -> Self {
+        let store = MemoryStore::new(config.max_entries);
+        Self::with_store(config, store)
+    }
+
+    /// Create a cache backed by `store`, which enforces its own size limits
+    pub fn with_store(config: CacheConfig, store: impl CacheStore + 'static) -> Self {
+        Self {
+            config,
+            store: Box::new(store),
+            stats: Mutex::new(CacheStats::default()),
+        }
+    }
+
+    /// The cache settings
+    pub fn config(&self) -> &CacheConfig {
+        &self.config
+    }
+
+    /// The backing store
+    pub fn store(&self) -> &dyn CacheStore {
+        self.store.as_ref()
+    }
+
+    /// Cache key for a request: the endpoint followed by its non-empty form fields in sorted order
+    pub fn key(endpoint: &str, form_data: &str) -> String {
+        let mut fields: Vec<(String, String)> =
+            serde_urlencoded::from_str(form_data).unwrap_or_default();
+        fields.retain(|(_, value)| !value.trim().is_empty());
+        fields.sort();
+        let canonical = serde_urlencoded::to_string(&fields).unwrap_or_default();
+        format!("{}?{}", endpoint, canonical)
+    }
+
+    /// Look up a fresh response, or any cached response when offline, counting a hit or
+    /// miss. Store errors count as misses.
+    pub fn get(&self, key: &str) -> Option<CachedResponse> {
+        let entry = self.store.get(key).ok().flatten();
+        let mut stats = self.lock_stats();
+        let Some(entry) = entry else {
+            stats.misses += 1;
+            return None;
+        };
+
+        if !self.config.offline && entry.is_expired(now_ms()) {
+            let _ = self.store.remove(key);
+            stats.expirations += 1;
+            stats.misses += 1;
+            return None;
+        }
+
+        stats.hits += 1;
+        if matches!(entry.response, CachedResponse::NotFound(_)) {
+            stats.negative_hits += 1;
+        }
+        Some(entry.response)
+    }
+
+    /// Store a response for `endpoint` with the TTL configured for it. Not-found results use
+    /// the negative TTL and are skipped when it is `None`.
+    pub fn insert(&self, endpoint: &str, key: String, response: CachedResponse) -> Result<()> {
+        let ttl = match &response {
+            CachedResponse::Found(_) => self.config.ttl_for(endpoint),
+            CachedResponse::NotFound(_) => match self.config.negative_ttl {
+                Some(ttl) => ttl,
+                None => return Ok(()),
+            },
+        };
+        if ttl.is_zero() {
+            return Ok(());
+        }
+
+        let fetched_at = now_ms();
+        let evicted = self.store.put(CacheEntry {
+            key,
+            endpoint: endpoint.to_string(),
+            response,
+            fetched_at,
+            expires_at: fetched_at.saturating_add(ttl.as_millis() as u64),
+        })?;
+        self.lock_stats().evictions += evicted as u64;
+        Ok(())
+    }
+
+    /// Drop the entry for `key`, returning whether there was one
+    pub fn invalidate(&self, key: &str) -> Result<bool> {
+        self.store.remove(key)
+    }
+
+    /// Drop the cached response for a request to `endpoint` with `params`
+    pub fn invalidate_request<T: Serialize>(&self, endpoint: &str, params: &T) -> Result<bool> {
+        let form_data = serde_urlencoded::to_string(params).map_err(|e| {
+            CufinderError::ValidationError(format!("Failed to encode form data: {}", e))
+        })?;
+        self.invalidate(&Self::key(endpoint, &form_data))
+    }
+
+    /// Drop everything cached about `subject`, such as an email or LinkedIn URL, returning
+    /// how many entries were deleted. See [`CacheEntry::mentions`].
+    pub fn purge_subject(&self, subject: &str) -> Result<usize> {
+        self.store.purge_subject(subject)
+    }
+
+    /// Drop every entry, keeping the statistics
+    pub fn clear(&self) -> Result<()> {
+        self.store.clear()
+    }
+
+    /// Write every entry to `path` as JSON lines, sorted by key, returning how many were written
+    pub fn export_jsonl(&self, path: impl AsRef<Path>) -> Result<usize> {
+        let mut entries = self.store.entries()?;
+        entries.sort_by(|a, b| a.key.cmp(&b.key));
+
+        let mut file = BufWriter::new(File::create(path)?);
+        for entry in &entries {
+            writeln!(file, "{}", serde_json::to_string(entry)?)?;
+        }
+        file.flush()?;
+        Ok(entries.len())
+    }
+
+    /// Load entries exported by [`export_jsonl`](Self::export_jsonl), replacing entries with
+    /// the same key and keeping their original fetch and expiry times. Returns how many were loaded.
+    pub fn import_jsonl(&self, path: impl AsRef<Path>) -> Result<usize> {
+        let mut imported = 0;
+        for line in BufReader::new(File::open(path)?).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry: CacheEntry = serde_json::from_str(&line)?;
+            let evicted = self.store.put(entry)?;
+            self.lock_stats().evictions += evicted as u64;
+            imported += 1;
+        }
+        Ok(imported)
+    }
+
+    /// Current hit, miss and eviction counts
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            entries: self.store.len(),
+            ..*self.lock_stats()
+        }
+    }
+
+    fn lock_stats(&self) -> MutexGuard<'_, CacheStats> {
+        self.stats.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,422 @@
+//! Storage backends for the response cache
+
+use crate::{
+    cache::CachedResponse,
+    error::{CufinderError, Result},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt, fs,
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// A cached response with its fetch and expiry times
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CacheEntry {
+    /// Endpoint plus canonical params, see [`ResponseCache::key`](crate::cache::ResponseCache::key)
+    pub key: String,
+    pub endpoint: String,
+    pub response: CachedResponse,
+    /// Unix time the response was fetched, in milliseconds
+    pub fetched_at: u64,
+    /// Unix time after which the entry is stale, in milliseconds
+    pub expires_at: u64,
+}
+
+impl CacheEntry {
+    /// Whether the entry is stale at unix time `now_ms`
+    pub fn is_expired(&self, now_ms: u64) -> bool {
+        self.expires_at <= now_ms
+    }
+
+    /// Whether the entry concerns `subject`, such as an email or LinkedIn URL: one of its
+    /// request params equals it, or the cached body mentions it. Case-insensitive.
+    pub fn mentions(&self, subject: &str) -> bool {
+        let subject = normalize_subject(subject);
+        if subject.is_empty() {
+            return false;
+        }
+
+        let query = self.key.split_once('?').map_or("", |(_, query)| query);
+        let fields: Vec<(String, String)> = serde_urlencoded::from_str(query).unwrap_or_default();
+        if fields
+            .iter()
+            .any(|(_, value)| normalize_subject(value) == subject)
+        {
+            return true;
+        }
+
+        let body = match &self.response {
+            CachedResponse::Found(value) => value.to_string(),
+            CachedResponse::NotFound(message) => message.clone(),
+        };
+        body.to_lowercase().contains(&subject)
+    }
+}
+
+fn normalize_subject(subject: &str) -> String {
+    subject.trim().trim_end_matches('/').to_lowercase()
+}
+
+/// Current unix time in milliseconds
+pub(crate) fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Where the response cache keeps its entries.
+///
+/// Stores enforce their own size limits; expiry is checked by the
+/// [`ResponseCache`](crate::cache::ResponseCache) on read.
+pub trait CacheStore: Send + Sync + fmt::Debug {
+    /// Look up the entry for `key`
+    fn get(&self, key: &str) -> Result<Option<CacheEntry>>;
+
+    /// Store `entry`, replacing any entry with the same key. Returns how many other
+    /// entries were evicted to make room.
+    fn put(&self, entry: CacheEntry) -> Result<usize>;
+
+    /// Delete the entry for `key`, returning whether there was one
+    fn remove(&self, key: &str) -> Result<bool>;
+
+    /// All stored entries, in no particular order
+    fn entries(&self) -> Result<Vec<CacheEntry>>;
+
+    /// Delete every entry
+    fn clear(&self) -> Result<()>;
+
+    /// Number of stored entries
+    fn len(&self) -> usize;
+
+    /// Whether the store holds no entries
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether `get` and `put` do blocking I/O, such as file or network access. The client
+    /// then calls them on a blocking thread instead of the async executor.
+    fn is_blocking(&self) -> bool {
+        false
+    }
+
+    /// Delete every entry that [mentions](CacheEntry::mentions) `subject`, returning how many were deleted
+    fn purge_subject(&self, subject: &str) -> Result<usize> {
+        let mut purged = 0;
+        for entry in self.entries()? {
+            if entry.mentions(subject) && self.remove(&entry.key)? {
+                purged += 1;
+            }
+        }
+        Ok(purged)
+    }
+}
+
+#[derive(Debug, Default)]
+struct MemoryState {
+    entries: HashMap<String, (CacheEntry, u64)>,
+    /// Entry keys by last use, oldest first
+    recency: BTreeMap<u64, String>,
+    tick: u64,
+}
+
+impl MemoryState {
+    fn remove(&mut self, key: &str) -> Option<CacheEntry> {
+        let (entry, last_used) = self.entries.remove(key)?;
+        self.recency.remove(&last_used);
+        Some(entry)
+    }
+}
+
+/// In-process LRU store, the default backend
+#[derive(Debug)]
+pub struct MemoryStore {
+    max_entries: usize,
+    state: Mutex<MemoryState>,
+}
+
+impl MemoryStore {
+    /// Keep at most `max_entries` entries, evicting the least recently used
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            max_entries,
+            state: Mutex::new(MemoryState::default()),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MemoryState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl CacheStore for MemoryStore {
+    fn get(&self, key: &str) -> Result<Option<CacheEntry>> {
+        let mut state = self.lock();
+        state.tick += 1;
+        let tick = state.tick;
+        let Some((entry, last_used)) = state.entries.get_mut(key) else {
+            return Ok(None);
+        };
+        let previous = std::mem::replace(last_used, tick);
+        let entry = entry.clone();
+        state.recency.remove(&previous);
+        state.recency.insert(tick, key.to_string());
+        Ok(Some(entry))
+    }
+
+    fn put(&self, entry: CacheEntry) -> Result<usize> {
+        if self.max_entries == 0 {
+            return Ok(0);
+        }
+
+        let mut state = self.lock();
+        state.remove(&entry.key);
+        let mut evicted = 0;
+        while state.entries.len() >= self.max_entries {
+            let Some((_, oldest)) = state.recency.pop_first() else {
+                break;
+            };
+            state.entries.remove(&oldest);
+            evicted += 1;
+        }
+
+        state.tick += 1;
+        let tick = state.tick;
+        state.recency.insert(tick, entry.key.clone());
+        state.entries.insert(entry.key.clone(), (entry, tick));
+        Ok(evicted)
+    }
+
+    fn remove(&self, key: &str) -> Result<bool> {
+        Ok(self.lock().remove(key).is_some())
+    }
+
+    fn entries(&self) -> Result<Vec<CacheEntry>> {
+        Ok(self
+            .lock()
+            .entries
+            .values()
+            .map(|(entry, _)| entry.clone())
+            .collect())
+    }
+
+    fn clear(&self) -> Result<()> {
+        let mut state = self.lock();
+        state.entries.clear();
+        state.recency.clear();
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FileIndexEntry {
+    file: PathBuf,
+    fetched_at: u64,
+    size: u64,
+}
+
+/// Persistent store keeping one JSON file per entry in a directory.
+///
+/// Entries survive restarts. When over its entry or byte limit, the store evicts the
+/// entries fetched longest ago. Its methods read and write files directly, so the client
+/// runs lookups and writes on a blocking thread; call them from async code the same way.
+#[derive(Debug)]
+pub struct FileStore {
+    dir: PathBuf,
+    max_entries: Option<usize>,
+    max_bytes: Option<u64>,
+    index: Mutex<HashMap<String, FileIndexEntry>>,
+}
+
+impl FileStore {
+    /// Open or create a store in `dir`, indexing any entries already there
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+
+        let mut index = HashMap::new();
+        for file in fs::read_dir(&dir)? {
+            let path = file?.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            // Unreadable or partially written files are skipped and overwritten later
+            let Ok(bytes) = fs::read(&path) else {
+                continue;
+            };
+            if let Ok(entry) = serde_json::from_slice::<CacheEntry>(&bytes) {
+                index.insert(
+                    entry.key,
+                    FileIndexEntry {
+                        file: path,
+                        fetched_at: entry.fetched_at,
+                        size: bytes.len() as u64,
+                    },
+                );
+            }
+        }
+
+        Ok(Self {
+            dir,
+            max_entries: None,
+            max_bytes: None,
+            index: Mutex::new(index),
+        })
+    }
+
+    /// Keep at most `max_entries` entries
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(max_entries);
+        self
+    }
+
+    /// Keep at most `max_bytes` bytes of entry files
+    pub fn max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Directory holding the entry files
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Total size of the entry files, in bytes
+    pub fn size_bytes(&self) -> u64 {
+        self.lock().values().map(|entry| entry.size).sum()
+    }
+
+    fn file_for(&self, key: &str) -> PathBuf {
+        self.dir
+            .join(format!("{:016x}.json", fnv1a(key.as_bytes())))
+    }
+
+    fn read(file: &Path) -> Result<Option<CacheEntry>> {
+        match fs::read(file) {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes).ok()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(CufinderError::IoError(e)),
+        }
+    }
+
+    fn delete(file: &Path) -> Result<()> {
+        match fs::remove_file(file) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(CufinderError::IoError(e)),
+            _ => Ok(()),
+        }
+    }
+
+    fn over_limit(&self, index: &HashMap<String, FileIndexEntry>) -> bool {
+        let entries_over = self.max_entries.is_some_and(|max| index.len() > max);
+        let bytes_over = self
+            .max_bytes
+            .is_some_and(|max| index.values().map(|entry| entry.size).sum::<u64>() > max);
+        entries_over || bytes_over
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, FileIndexEntry>> {
+        self.index.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl CacheStore for FileStore {
+    fn get(&self, key: &str) -> Result<Option<CacheEntry>> {
+        let Some(file) = self.lock().get(key).map(|entry| entry.file.clone()) else {
+            return Ok(None);
+        };
+        Ok(Self::read(&file)?.filter(|entry| entry.key == key))
+    }
+
+    fn put(&self, entry: CacheEntry) -> Result<usize> {
+        let file = self.file_for(&entry.key);
+        let bytes = serde_json::to_vec(&entry)?;
+
+        let tmp = file.with_extension("json.tmp");
+        fs::write(&tmp, &bytes)?;
+        fs::rename(&tmp, &file)?;
+
+        let mut index = self.lock();
+        // Another key hashing to the same file has just been overwritten
+        index.retain(|key, existing| existing.file != file || *key == entry.key);
+        index.insert(
+            entry.key.clone(),
+            FileIndexEntry {
+                file,
+                fetched_at: entry.fetched_at,
+                size: bytes.len() as u64,
+            },
+        );
+
+        let mut evicted = 0;
+        while self.over_limit(&index) {
+            let oldest = index
+                .iter()
+                .filter(|(key, _)| **key != entry.key)
+                .min_by_key(|(_, existing)| existing.fetched_at)
+                .map(|(key, _)| key.clone());
+            let Some(oldest) = oldest else {
+                break;
+            };
+            if let Some(removed) = index.remove(&oldest) {
+                Self::delete(&removed.file)?;
+                evicted += 1;
+            }
+        }
+        Ok(evicted)
+    }
+
+    fn remove(&self, key: &str) -> Result<bool> {
+        match self.lock().remove(key) {
+            Some(entry) => Self::delete(&entry.file).map(|_| true),
+            None => Ok(false),
+        }
+    }
+
+    fn entries(&self) -> Result<Vec<CacheEntry>> {
+        let files: Vec<(String, PathBuf)> = self
+            .lock()
+            .iter()
+            .map(|(key, entry)| (key.clone(), entry.file.clone()))
+            .collect();
+
+        let mut entries = Vec::with_capacity(files.len());
+        for (key, file) in files {
+            if let Some(entry) = Self::read(&file)?.filter(|entry| entry.key == key) {
+                entries.push(entry);
+            }
+        }
+        Ok(entries)
+    }
+
+    fn clear(&self) -> Result<()> {
+        let mut index = self.lock();
+        for entry in index.values() {
+            Self::delete(&entry.file)?;
+        }
+        index.clear();
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    fn is_blocking(&self) -> bool {
+        true
+    }
+}
+
+/// 64-bit FNV-1a, a hash that is stable across builds for naming entry files
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
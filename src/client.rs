@@ -1,7 +1,37 @@
+use crate::cache::{CacheConfig, CachedResponse, ResponseCache};
+use crate::cache_store::CacheStore;
+use crate::cache_store::now_ms;
+use crate::credits::{CreditAccountant, CreditBudget};
+use crate::envelope::{ApiEnvelope, ResponseBody, Usage};
 use crate::error::{CufinderError, Result};
+use crate::events::{Event, EventSink, Observer};
+use crate::ledger::{LedgerEntry, LedgerSink};
+#[cfg(feature = "audit")]
+use crate::audit::{AuditLog, AuditOutcome};
+#[cfg(feature = "metrics")]
+use crate::metrics::MetricsRecorder;
+use crate::rate_limit::RateLimiter;
+use crate::single_flight::SingleFlight;
+use crate::trace::{CacheOutcome, RequestSpan};
 use reqwest::Client as ReqwestClient;
+use reqwest::header::HeaderMap;
 use serde::Serialize;
-use std::time::Duration;
+use serde::de::DeserializeOwned;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant};
+
+/// Delay before the first retry, doubled on each further attempt
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(250);
+
+/// Upper bound on the delay between retries
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(10);
+
+tokio::task_local! {
+    /// HTTP requests sent by the calls running inside [`count_sends`]
+    static SENDS: Arc<AtomicU32>;
+}
 
 /// Configuration for the CUFinder client
 #[derive(Debug, Clone)]
@@ -23,11 +53,53 @@ impl Default for ClientConfig {
     }
 }
 
+/// A fully encoded request, as returned by [`Client::prepare`] and in dry-run mode
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PreparedRequest {
+    pub method: String,
+    pub endpoint: String,
+    pub url: String,
+    /// Request headers, with the API key redacted
+    pub headers: Vec<(String, String)>,
+    /// Form-encoded body
+    pub body: String,
+}
+
+impl std::fmt::Display for PreparedRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.method, self.url, self.body)
+    }
+}
+
+/// A successful response as received, returned by [`Client::post_raw`]
+#[derive(Debug, Clone)]
+pub struct RawResponse {
+    pub status: u16,
+    pub headers: HeaderMap,
+    /// Response body exactly as sent by the API
+    pub body: String,
+    /// The body with its `data` envelope unwrapped, as [`Client::post`] returns it
+    pub data: serde_json::Value,
+}
+
 /// HTTP client for CUFinder API
 #[derive(Debug, Clone)]
 pub struct Client {
     config: ClientConfig,
     http_client: ReqwestClient,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    cache: Option<Arc<ResponseCache>>,
+    single_flight: Option<Arc<SingleFlight>>,
+    credits: Arc<CreditAccountant>,
+    ledger: Option<Arc<dyn LedgerSink>>,
+    tag: Option<Arc<str>>,
+    #[cfg(feature = "audit")]
+    audit: Option<Arc<AuditLog>>,
+    dry_run: bool,
+    retry_timeouts: bool,
+    #[cfg(feature = "tracing")]
+    trace_params: bool,
+    events: EventSink,
 }
 
 impl Client {
@@ -41,6 +113,19 @@ impl Client {
         Ok(Self {
             config,
             http_client,
+            rate_limiter: None,
+            cache: None,
+            single_flight: Some(Arc::default()),
+            credits: Arc::default(),
+            ledger: None,
+            tag: None,
+            #[cfg(feature = "audit")]
+            audit: None,
+            dry_run: false,
+            retry_timeouts: false,
+            #[cfg(feature = "tracing")]
+            trace_params: false,
+            events: EventSink::default(),
         })
     }
 
@@ -52,64 +137,543 @@ impl Client {
         })
     }
 
-    /// Send a POST request to the API
+    /// Limit outgoing requests to `requests_per_second`, shared by all clones of this client.
+    ///
+    /// # Panics
+    ///
+    /// If the rate is invalid, see [`RateLimiter::try_new`].
+    pub fn with_rate_limit(mut self, requests_per_second: f64) -> Self {
+        self.rate_limiter = Some(Arc::new(RateLimiter::new(requests_per_second)));
+        self
+    }
+
+    /// Like [`with_rate_limit`](Self::with_rate_limit), but fails with
+    /// [`CufinderError::ValidationError`] instead of panicking on an invalid rate
+    pub fn try_with_rate_limit(mut self, requests_per_second: f64) -> Result<Self> {
+        self.rate_limiter = Some(Arc::new(RateLimiter::try_new(requests_per_second)?));
+        Ok(self)
+    }
+
+    /// Cache responses in memory so repeated requests do not spend credits,
+    /// shared by all clones of this client
+    pub fn with_cache(mut self, config: CacheConfig) -> Self {
+        self.cache = Some(Arc::new(ResponseCache::new(config)));
+        self
+    }
+
+    /// Cache responses in `store`, such as a persistent [`FileStore`](crate::cache_store::FileStore)
+    pub fn with_cache_store(mut self, config: CacheConfig, store: impl CacheStore + 'static) -> Self {
+        self.cache = Some(Arc::new(ResponseCache::with_store(config, store)));
+        self
+    }
+
+    /// Get the response cache, if one is configured
+    pub fn cache(&self) -> Option<&ResponseCache> {
+        self.cache.as_deref()
+    }
+
+    /// Coalesce concurrent identical requests (same endpoint and params) into one HTTP call
+    /// whose result, success or error, is shared by every caller. Enabled by default.
+    pub fn with_coalescing(mut self, enabled: bool) -> Self {
+        self.single_flight = enabled.then(Arc::default);
+        self
+    }
+
+    /// Retry requests that timed out. Off by default: a request that timed out may have
+    /// been accepted and charged by the API, so retrying it can charge the lookup twice.
+    pub fn with_retry_timeouts(mut self, enabled: bool) -> Self {
+        self.retry_timeouts = enabled;
+        self
+    }
+
+    /// Number of calls answered by sharing an identical in-flight request instead of sending one
+    pub fn saved_calls(&self) -> u64 {
+        self.single_flight.as_ref().map_or(0, |group| group.saved())
+    }
+
+    /// Track credits against `budget`, starting from zero
+    pub fn with_budget(mut self, budget: CreditBudget) -> Self {
+        self.credits = Arc::new(CreditAccountant::new(budget));
+        self
+    }
+
+    /// A clone of this client whose credits are tracked in a new scope under `budget`,
+    /// still charging and obeying this client's budget
+    pub fn scoped(&self, budget: CreditBudget) -> Self {
+        Self {
+            credits: self.credits.scoped(budget),
+            ..self.clone()
+        }
+    }
+
+    /// Credits spent through this client and its clones
+    pub fn credits(&self) -> &CreditAccountant {
+        &self.credits
+    }
+
+    /// In dry-run mode nothing is sent: [`post`](Self::post) fails with
+    /// [`CufinderError::DryRun`] holding the request it would have sent
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Record request parameters, which may contain personal data, in `tracing` spans
+    #[cfg(feature = "tracing")]
+    pub fn with_trace_params(mut self, enabled: bool) -> Self {
+        self.trace_params = enabled;
+        self
+    }
+
+    /// Record every call made through this client and its clones in `ledger`
+    pub fn with_ledger(mut self, ledger: impl LedgerSink + 'static) -> Self {
+        self.ledger = Some(Arc::new(ledger));
+        self
+    }
+
+    /// Append a record of every call to a personal-data endpoint to `audit`
+    #[cfg(feature = "audit")]
+    pub fn with_audit(mut self, audit: AuditLog) -> Self {
+        self.audit = Some(Arc::new(audit));
+        self
+    }
+
+    /// A clone of this client whose ledger entries carry `tag`, such as a team name
+    pub fn tagged(&self, tag: impl Into<String>) -> Self {
+        Self {
+            tag: Some(tag.into().into()),
+            ..self.clone()
+        }
+    }
+
+    /// Report request, retry, rate-limit and credit [`Event`]s to `observer`
+    pub fn with_observer(mut self, observer: impl Observer + 'static) -> Self {
+        self.events = self.events.clone().with_observer(Arc::new(observer));
+        self
+    }
+
+    /// Record request, error, latency, retry, cache and credit metrics in `metrics`
+    #[cfg(feature = "metrics")]
+    pub fn with_metrics(mut self, metrics: impl MetricsRecorder + 'static) -> Self {
+        self.events = self.events.clone().with_metrics(Arc::new(metrics));
+        self
+    }
+
+    pub(crate) fn events(&self) -> &EventSink {
+        &self.events
+    }
+
+    /// Get the client-side rate limiter, if one is configured
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_deref()
+    }
+
+    /// Send a POST request to the API.
+    ///
+    /// Retryable failures (rate limiting, server errors, connection failures) are retried up
+    /// to `max_retries` times with exponential backoff, honoring `Retry-After` when sent. A
+    /// `Retry-After` longer than 10 seconds fails the call with the error instead. Timeouts
+    /// are only retried with [`with_retry_timeouts`](Self::with_retry_timeouts).
+    /// With a cache configured, fresh cached responses and not-found results are returned
+    /// without a request. Responses that cost this caller nothing, from the cache or shared
+    /// with an identical in-flight request, report a `credit_count` of 0. An offline cache
+    /// never sends requests and returns [`CufinderError::CacheMiss`] instead. Concurrent
+    /// identical requests share one HTTP call unless coalescing is disabled. Once a hard
+    /// credit limit is reached, requests fail with [`CufinderError::BudgetExceeded`] unsent.
     pub async fn post<T>(&self, endpoint: &str, data: &T) -> Result<serde_json::Value>
+    where
+        T: Serialize,
+    {
+        self.request(endpoint, data, |body| {
+            let (envelope, usage) = body.decode::<serde_json::Value>()?;
+            Ok((envelope.into_merged(), usage))
+        })
+        .await
+    }
+
+    /// Send a POST request like [`post`](Self::post) and decode the response envelope and its
+    /// `data` as `R` straight from the response body.
+    ///
+    /// Responses answered from the cache or shared with an identical in-flight request are
+    /// already unwrapped, so they have no envelope fields and carry `meta_data` in `data`.
+    pub async fn post_envelope<T, R>(&self, endpoint: &str, data: &T) -> Result<ApiEnvelope<R>>
+    where
+        T: Serialize,
+        R: DeserializeOwned,
+    {
+        self.request(endpoint, data, ResponseBody::decode).await
+    }
+
+    /// Send a request and decode its response with `decode`, which also returns its usage
+    async fn request<T, R>(&self, endpoint: &str, data: &T, decode: impl FnOnce(&ResponseBody) -> Result<(R, Usage)>) -> Result<R>
     where
         T: Serialize,
     {
         let url = format!("{}{}", self.config.base_url, endpoint);
         
         // Convert data to form-encoded format
-        let form_data = serde_urlencoded::to_string(data)
+        let form_data = self.encode(endpoint, data)?;
+
+        if self.dry_run {
+            return Err(CufinderError::DryRun(Box::new(self.prepare(endpoint, data)?)));
+        }
+
+        let span = RequestSpan::new(endpoint, self.traced_params(&form_data));
+        let started = Instant::now();
+        let (result, source) = span.run(self.dispatch(endpoint, &url, &form_data, &span)).await;
+        // Usage is read while decoding. A body that does not decode as `R` is scanned for it
+        // separately, since the API may have charged the call all the same.
+        let result = result.and_then(|body| match decode(&body) {
+            Ok((response, usage)) => Ok((Ok(response), usage)),
+            Err(error) => {
+                self.call_failed(endpoint, &error);
+                body.usage().map(|usage| (Err(error), usage))
+            }
+        });
+        let usage = result.as_ref().map(|(_, usage)| *usage);
+        if source == Source::Network && let Ok(usage) = usage {
+            self.record_credits(endpoint, usage);
+        }
+        let cache = match source {
+            Source::Cache => CacheOutcome::Hit,
+            Source::Shared => CacheOutcome::Shared,
+            Source::Network if self.cache.as_deref().is_some_and(|cache| cache.config().is_cacheable(endpoint)) => CacheOutcome::Miss,
+            Source::Network => CacheOutcome::Bypass,
+        };
+        span.finish(cache, usage.as_ref().ok().copied(), result.as_ref().err().map(CufinderError::kind), started.elapsed());
+        self.record_call(endpoint, usage, source).await;
+        #[cfg(feature = "audit")]
+        self.audit_call(endpoint, &form_data, usage, source).await?;
+        result.and_then(|(response, _)| response)
+    }
+
+    /// Send a POST request to any endpoint and return the response with its status,
+    /// headers and untouched body.
+    ///
+    /// Requests are retried, rate limited, charged to the credit budget and written to the
+    /// ledger like [`post`](Self::post), but always go to the network: the cache and
+    /// coalescing of identical requests are bypassed. An offline cache holds no raw
+    /// responses, so offline every call fails with [`CufinderError::CacheMiss`] unsent.
+    pub async fn post_raw<T>(&self, endpoint: &str, data: &T) -> Result<RawResponse>
+    where
+        T: Serialize,
+    {
+        let url = format!("{}{}", self.config.base_url, endpoint);
+        let form_data = self.encode(endpoint, data)?;
+
+        if self.dry_run {
+            return Err(CufinderError::DryRun(Box::new(self.prepare(endpoint, data)?)));
+        }
+
+        if self.cache.as_deref().is_some_and(|cache| cache.config().offline) {
+            let error = CufinderError::CacheMiss(ResponseCache::key(endpoint, &form_data));
+            self.call_failed(endpoint, &error);
+            self.record_call(endpoint, Err(&error), Source::Cache).await;
+            #[cfg(feature = "audit")]
+            self.audit_call(endpoint, &form_data, Err(&error), Source::Cache).await?;
+            return Err(error);
+        }
+
+        self.credits.check().inspect_err(|error| self.call_failed(endpoint, error))?;
+        let span = RequestSpan::new(endpoint, self.traced_params(&form_data));
+        let started = Instant::now();
+        let result = span.run(self.post_uncached(endpoint, &url, &form_data, &span)).await;
+        let result = result.and_then(|received| match ApiEnvelope::<serde_json::Value>::from_body(&received.body) {
+            Ok(envelope) => Ok((received, envelope.into_merged())),
+            Err(error) => {
+                self.call_failed(endpoint, &error);
+                Err(error)
+            }
+        });
+        let usage = result.as_ref().map(|(_, data)| Usage::from_value(data));
+        if let Ok(usage) = usage {
+            self.record_credits(endpoint, usage);
+        }
+        span.finish(CacheOutcome::Bypass, usage.as_ref().ok().copied(), result.as_ref().err().map(CufinderError::kind), started.elapsed());
+        self.record_call(endpoint, usage, Source::Network).await;
+        #[cfg(feature = "audit")]
+        self.audit_call(endpoint, &form_data, usage, Source::Network).await?;
+        let (received, data) = result?;
+        Ok(RawResponse {
+            status: received.status,
+            headers: received.headers,
+            body: received.body,
+            data,
+        })
+    }
+
+    /// Answer a request from the cache, an identical in-flight request or the network
+    async fn dispatch(&self, endpoint: &str, url: &str, form_data: &str, span: &RequestSpan) -> (Result<ResponseBody>, Source) {
+        let key = ResponseCache::key(endpoint, form_data);
+        let cache = self.cache.as_ref().filter(|cache| cache.config().is_cacheable(endpoint));
+        if let Some(cache) = cache {
+            let cached = cache_get(cache, &key).await;
+            if cached.is_none() && cache.config().offline {
+                let error = CufinderError::CacheMiss(key);
+                self.call_failed(endpoint, &error);
+                return (Err(error), Source::Cache);
+            }
+            if let Some(cached) = cached {
+                self.events.emit(|| Event::CacheHit { endpoint: endpoint.to_string() });
+                let result = match cached {
+                    CachedResponse::Found(response) => Ok(ResponseBody::Decoded(uncharged(response))),
+                    CachedResponse::NotFound(message) => Err(CufinderError::ApiError { status: 404, message }),
+                };
+                return (result, Source::Cache);
+            }
+        }
+
+        if let Err(error) = self.credits.check() {
+            self.call_failed(endpoint, &error);
+            return (Err(error), Source::Network);
+        }
+        let (result, shared) = match &self.single_flight {
+            Some(group) => group.run(&key, || self.post_data(endpoint, url, form_data, span)).await,
+            None => (self.post_data(endpoint, url, form_data, span).await, false),
+        };
+        if shared {
+            self.events.emit(|| Event::RequestCoalesced { endpoint: endpoint.to_string() });
+            let result = result.and_then(ResponseBody::into_value).map(|response| ResponseBody::Decoded(uncharged(response)));
+            return (result, Source::Shared);
+        }
+
+        // A cache that cannot be written to only costs credits later, so its errors are ignored
+        if let Some(cache) = cache {
+            let response = match &result {
+                Ok(response) => response.clone().into_value().ok().map(CachedResponse::Found),
+                Err(CufinderError::ApiError { status: 404, message }) => Some(CachedResponse::NotFound(message.clone())),
+                Err(_) => None,
+            };
+            if let Some(response) = response {
+                let _ = cache_insert(cache, endpoint, key, response).await;
+            }
+        }
+        (result, Source::Network)
+    }
+
+    /// Form-encode the parameters of a call to `endpoint`
+    fn encode<T: Serialize>(&self, endpoint: &str, data: &T) -> Result<String> {
+        serde_urlencoded::to_string(data)
+            .map_err(|e| CufinderError::ValidationError(format!("Failed to encode form data: {}", e)))
+            .inspect_err(|error| self.call_failed(endpoint, error))
+    }
+
+    /// Report a call to `endpoint` that failed without an HTTP request reporting the error
+    pub(crate) fn call_failed(&self, endpoint: &str, error: &CufinderError) {
+        self.events.emit(|| Event::CallFailed { endpoint: endpoint.to_string(), error: error.kind() });
+    }
+
+    /// Request parameters to record in the call's span, if enabled
+    fn traced_params<'a>(&self, form_data: &'a str) -> Option<&'a str> {
+        #[cfg(feature = "tracing")]
+        return self.trace_params.then_some(form_data);
+        #[cfg(not(feature = "tracing"))]
+        {
+            let _ = form_data;
+            None
+        }
+    }
+
+    /// Write a call to the audit log, if configured and the endpoint is audited.
+    ///
+    /// The record is written on a blocking thread. A failed write is reported as an
+    /// [`Event::AuditWriteFailed`], and fails the call only in strict mode.
+    #[cfg(feature = "audit")]
+    async fn audit_call(&self, endpoint: &str, form_data: &str, result: std::result::Result<Usage, &CufinderError>, source: Source) -> Result<()> {
+        let Some(audit) = self.audit.clone().filter(|audit| audit.config().audits(endpoint)) else {
+            return Ok(());
+        };
+        let outcome = match result {
+            Ok(_) => AuditOutcome::Success,
+            Err(error) => AuditOutcome::Error { kind: error.kind() },
+        };
+        let strict = audit.config().strict;
+        let (path, tag, params) = (endpoint.to_string(), self.tag.clone(), form_data.to_string());
+        let written = tokio::task::spawn_blocking(move || audit.record(&path, tag.as_deref(), &params, outcome, source == Source::Cache))
+            .await
+            .unwrap_or_else(|e| Err(CufinderError::UnknownError(format!("audit writer failed: {}", e))));
+
+        match written {
+            Err(error) => {
+                self.events.emit(|| Event::AuditWriteFailed {
+                    endpoint: endpoint.to_string(),
+                    error: error.kind(),
+                    message: error.to_string(),
+                });
+                if strict { Err(error) } else { Ok(()) }
+            }
+            Ok(()) => Ok(()),
+        }
+    }
+
+    /// Count the credits of a response this caller was charged for
+    fn record_credits(&self, endpoint: &str, usage: Usage) {
+        if let Some(credits) = usage.credit_count {
+            self.credits.record(endpoint, credits);
+            let credits = credits as i32;
+            self.events.emit(|| Event::CreditsConsumed { endpoint: endpoint.to_string(), credits });
+        }
+    }
+
+    /// Write a call to the ledger, if one is configured, on a blocking thread if the sink
+    /// does blocking I/O
+    async fn record_call(&self, endpoint: &str, result: std::result::Result<Usage, &CufinderError>, source: Source) {
+        let Some(ledger) = &self.ledger else {
+            return;
+        };
+
+        let usage = result.unwrap_or_default();
+        let entry = LedgerEntry {
+            timestamp: now_ms(),
+            endpoint: endpoint.to_string(),
+            credits: if source == Source::Network { usage.credit_count.unwrap_or(0) } else { 0 },
+            confidence_level: usage.confidence_level.map(|c| c as i32),
+            cache_hit: source == Source::Cache,
+            shared: source == Source::Shared,
+            tag: self.tag.as_deref().map(str::to_string),
+            error: result.err().map(CufinderError::kind),
+        };
+        // Losing a ledger line is better than failing a call whose credits are already spent
+        if ledger.is_blocking() {
+            let ledger = ledger.clone();
+            let _ = tokio::task::spawn_blocking(move || ledger.record(&entry)).await;
+        } else {
+            let _ = ledger.record(&entry);
+        }
+    }
+
+    /// Send a request, retrying retryable failures, and return its still encoded body
+    async fn post_data(&self, endpoint: &str, url: &str, form_data: &str, span: &RequestSpan) -> Result<ResponseBody> {
+        let received = self.post_uncached(endpoint, url, form_data, span).await?;
+        Ok(ResponseBody::Encoded(Arc::new(received.body)))
+    }
+
+    /// Send a request, retrying retryable failures
+    async fn post_uncached(&self, endpoint: &str, url: &str, form_data: &str, span: &RequestSpan) -> Result<Received> {
+        let mut attempt = 0;
+        loop {
+            if let Some(limiter) = &self.rate_limiter {
+                let wait = limiter.acquire().await;
+                if !wait.is_zero() {
+                    self.events.emit(|| Event::RateLimitWait { endpoint: endpoint.to_string(), wait });
+                }
+            }
+
+            self.events.emit(|| Event::RequestStarted { endpoint: endpoint.to_string(), attempt });
+            let started = Instant::now();
+            span.record_attempt(attempt);
+            let _ = SENDS.try_with(|sends| sends.fetch_add(1, Ordering::Relaxed));
+            let result = self.send(url, form_data, span).await;
+            self.events.emit(|| Event::RequestFinished {
+                endpoint: endpoint.to_string(),
+                attempt,
+                latency: started.elapsed(),
+                error: result.as_ref().err().map(|(error, _)| error.kind()),
+            });
+
+            match result {
+                Ok(response) => return Ok(response),
+                Err((error, retry_after))
+                    if attempt < self.config.max_retries
+                        && (error.is_retryable() || (self.retry_timeouts && error.is_timeout()))
+                        && retry_after.is_none_or(|delay| delay <= RETRY_MAX_DELAY) =>
+                {
+                    let delay = retry_after.unwrap_or_else(|| retry_delay(attempt));
+                    self.events.emit(|| Event::RetryScheduled {
+                        endpoint: endpoint.to_string(),
+                        attempt,
+                        delay,
+                        error: error.kind(),
+                    });
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err((error, _)) => return Err(error),
+            }
+        }
+    }
+
+    /// Headers sent with every request
+    fn headers(&self) -> [(&'static str, &str); 3] {
+        [
+            ("x-api-key", &self.config.api_key),
+            ("Content-Type", "application/x-www-form-urlencoded"),
+            ("User-Agent", "cufinder-rust/1.2.0"),
+        ]
+    }
+
+    /// Encode a request exactly as [`post`](Self::post) would send it, with the API key redacted
+    pub fn prepare<T>(&self, endpoint: &str, data: &T) -> Result<PreparedRequest>
+    where
+        T: Serialize,
+    {
+        let body = serde_urlencoded::to_string(data)
             .map_err(|e| CufinderError::ValidationError(format!("Failed to encode form data: {}", e)))?;
-        
-        let response = self
-            .http_client
-            .post(&url)
-            .header("x-api-key", &self.config.api_key)
-            .header("Content-Type", "application/x-www-form-urlencoded")
-            .header("User-Agent", "cufinder-rust/1.2.0")
-            .body(form_data)
+        let headers = self
+            .headers()
+            .into_iter()
+            .map(|(name, value)| {
+                let value = if name == "x-api-key" { redact(value) } else { value.to_string() };
+                (name.to_string(), value)
+            })
+            .collect();
+
+        Ok(PreparedRequest {
+            method: "POST".to_string(),
+            endpoint: endpoint.to_string(),
+            url: format!("{}{}", self.config.base_url, endpoint),
+            headers,
+            body,
+        })
+    }
+
+    /// Send a single request, returning the error and any `Retry-After` delay on failure
+    async fn send(&self, url: &str, form_data: &str, span: &RequestSpan) -> std::result::Result<Received, (CufinderError, Option<Duration>)> {
+        let mut request = self.http_client.post(url);
+        for (name, value) in self.headers() {
+            request = request.header(name, value);
+        }
+        let response = request
+            .body(form_data.to_string())
             .send()
             .await
-            .map_err(CufinderError::HttpError)?;
+            .map_err(|e| (CufinderError::HttpError(e), None))?;
 
         let status = response.status();
+        span.record_status(status.as_u16());
         
         if !status.is_success() {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .map(Duration::from_secs);
             let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
             
-            return match status.as_u16() {
-                401 => Err(CufinderError::AuthenticationError(error_text)),
-                429 => Err(CufinderError::RateLimitError(error_text)),
-                402 => Err(CufinderError::CreditLimitError(error_text)),
-                _ => Err(CufinderError::ApiError {
+            let error = match status.as_u16() {
+                401 => CufinderError::AuthenticationError(error_text),
+                429 => CufinderError::RateLimitError(error_text),
+                402 => CufinderError::CreditLimitError(error_text),
+                _ => CufinderError::ApiError {
                     status: status.as_u16(),
                     message: error_text,
-                }),
+                },
             };
+            return Err((error, retry_after));
         }
 
-        let mut json_response: serde_json::Value = response
-            .json()
+        let headers = response.headers().clone();
+        let body = response
+            .text()
             .await
-            .map_err(CufinderError::HttpError)?;
-
-        // Check if the response has a "data" wrapper and extract it
-        if let Some(data_wrapper) = json_response.get("data") {
-            if let Some(meta_data) = json_response.get("meta_data") {
-                // Create a new object with data content plus meta_data
-                let mut data_obj = data_wrapper.clone();
-                if let serde_json::Value::Object(ref mut map) = data_obj {
-                    map.insert("meta_data".to_string(), meta_data.clone());
-                }
-                json_response = data_obj;
-            } else {
-                json_response = data_wrapper.clone();
-            }
-        }
-
-        Ok(json_response)
+            .map_err(|e| (CufinderError::HttpError(e), None))?;
+        // The body is decoded, and its credits read, once its type is known
+        Ok(Received {
+            status: status.as_u16(),
+            headers,
+            body,
+        })
     }
 
     /// Get the underlying HTTP client for advanced usage
@@ -122,3 +686,71 @@ impl Client {
         &self.config
     }
 }
+
+/// Hide all but the last four characters of a secret
+fn redact(secret: &str) -> String {
+    let chars: Vec<char> = secret.chars().collect();
+    if chars.len() <= 8 {
+        return "****".to_string();
+    }
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("****{}", tail)
+}
+
+/// A successful response as received, before decoding
+struct Received {
+    status: u16,
+    headers: HeaderMap,
+    body: String,
+}
+
+/// Where the result of a call came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Source {
+    Network,
+    Cache,
+    Shared,
+}
+
+/// Look up `key` in `cache`, on a blocking thread if its store does blocking I/O
+async fn cache_get(cache: &Arc<ResponseCache>, key: &str) -> Option<CachedResponse> {
+    if !cache.store().is_blocking() {
+        return cache.get(key);
+    }
+    let (cache, key) = (cache.clone(), key.to_string());
+    tokio::task::spawn_blocking(move || cache.get(&key)).await.ok().flatten()
+}
+
+/// Store a response in `cache`, on a blocking thread if its store does blocking I/O
+async fn cache_insert(cache: &Arc<ResponseCache>, endpoint: &str, key: String, response: CachedResponse) -> Result<()> {
+    if !cache.store().is_blocking() {
+        return cache.insert(endpoint, key, response);
+    }
+    let (cache, endpoint) = (cache.clone(), endpoint.to_string());
+    tokio::task::spawn_blocking(move || cache.insert(&endpoint, key, response))
+        .await
+        .unwrap_or_else(|e| Err(CufinderError::UnknownError(format!("cache writer failed: {}", e))))
+}
+
+/// Mark a response that did not cost this caller anything with a `credit_count` of 0
+fn uncharged(mut response: serde_json::Value) -> serde_json::Value {
+    if let Some(credits) = response.get_mut("credit_count") {
+        *credits = serde_json::Value::from(0);
+    }
+    response
+}
+
+/// Run `future` and count the HTTP requests it sends, retries included. Requests answered
+/// from the cache or by an identical request in flight elsewhere are not counted.
+pub(crate) async fn count_sends<F: Future>(future: F) -> (F::Output, u32) {
+    let sends = Arc::new(AtomicU32::new(0));
+    let output = SENDS.scope(sends.clone(), future).await;
+    (output, sends.load(Ordering::Relaxed))
+}
+
+/// Exponential backoff delay before retry number `attempt + 1`
+pub(crate) fn retry_delay(attempt: u32) -> Duration {
+    RETRY_BASE_DELAY
+        .saturating_mul(2u32.saturating_pow(attempt))
+        .min(RETRY_MAX_DELAY)
+}
@@ -0,0 +1,131 @@
+//! Pre-flight credit estimates for batches and paginated searches
+
+use crate::{ledger::LedgerReport, pagination::PaginationOptions};
+use std::{
+    collections::HashMap,
+    iter::Sum,
+    ops::{Add, AddAssign},
+};
+
+/// Credits charged per request, by endpoint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CostTable {
+    /// Credits for endpoints without an entry
+    pub default_cost: i64,
+    /// Credits per request, keyed by path such as `"/enc"`
+    pub endpoints: HashMap<String, i64>,
+}
+
+impl Default for CostTable {
+    fn default() -> Self {
+        Self {
+            default_cost: 1,
+            endpoints: HashMap::new(),
+        }
+    }
+}
+
+impl CostTable {
+    /// A table charging one credit per request until configured
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Charge `credits` per request to endpoints without an entry
+    pub fn default_cost(mut self, credits: i64) -> Self {
+        self.default_cost = credits;
+        self
+    }
+
+    /// Charge `credits` per request to `endpoint`
+    pub fn endpoint(mut self, endpoint: impl Into<String>, credits: i64) -> Self {
+        self.endpoints.insert(endpoint.into(), credits);
+        self
+    }
+
+    /// A table of the average credits per charged request observed in a ledger, rounded up
+    pub fn from_report(report: &LedgerReport) -> Self {
+        let mut table = Self::new();
+        for (endpoint, totals) in &report.by_endpoint {
+            if totals.charged > 0 {
+                let average = (totals.charged_credits as f64 / totals.charged as f64).ceil() as i64;
+                table.endpoints.insert(endpoint.clone(), average);
+            }
+        }
+        table
+    }
+
+    /// Credits per request to `endpoint`
+    pub fn cost(&self, endpoint: &str) -> i64 {
+        self.endpoints
+            .get(endpoint)
+            .copied()
+            .unwrap_or(self.default_cost)
+    }
+
+    /// Cost of `requests` requests to `endpoint`, such as a batch over that many inputs
+    pub fn estimate(&self, endpoint: &str, requests: u64) -> CostEstimate {
+        CostEstimate {
+            requests,
+            credits: self.cost(endpoint).saturating_mul(requests as i64),
+        }
+    }
+
+    /// Cost of walking a paginated search on `endpoint` with `options`, given its page size
+    /// and, if known, the total number of results.
+    ///
+    /// Returns `None` when nothing bounds the search: no total, `max_pages` or `max_items`.
+    pub fn estimate_pages(
+        &self,
+        endpoint: &str,
+        options: &PaginationOptions,
+        page_size: usize,
+        total_items: Option<u64>,
+    ) -> Option<CostEstimate> {
+        let page_size = page_size.max(1) as u64;
+        let skipped = (options.start_page.unwrap_or(1).max(1) as u64 - 1) * page_size;
+        let remaining = total_items.map(|total| total.saturating_sub(skipped));
+
+        let items = match (remaining, options.max_items) {
+            (Some(remaining), Some(max)) => Some(remaining.min(max as u64)),
+            (remaining, max) => remaining.or(max.map(|max| max as u64)),
+        };
+        let pages_for_items = items.map(|items| items.div_ceil(page_size));
+        let pages = match (pages_for_items, options.max_pages) {
+            (Some(pages), Some(max)) => pages.min(max as u64),
+            (pages, max) => pages.or(max.map(|max| max as u64))?,
+        };
+
+        Some(self.estimate(endpoint, pages))
+    }
+}
+
+/// Estimated requests and credits
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CostEstimate {
+    pub requests: u64,
+    pub credits: i64,
+}
+
+impl Add for CostEstimate {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            requests: self.requests + other.requests,
+            credits: self.credits + other.credits,
+        }
+    }
+}
+
+impl AddAssign for CostEstimate {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sum for CostEstimate {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
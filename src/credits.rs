@@ -0,0 +1,198 @@
+//! Credit accounting and budgets
+
+use crate::error::{CufinderError, Result};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+type SoftLimitCallback = Arc<dyn Fn(&CreditUsage) + Send + Sync>;
+
+/// Limits on the credits an SDK, or a scope of it, may spend
+#[derive(Clone, Default)]
+pub struct CreditBudget {
+    /// Credits after which the soft-limit callback runs, once
+    pub soft_limit: Option<i64>,
+    /// Credits after which further calls fail with [`CufinderError::BudgetExceeded`] before sending
+    pub hard_limit: Option<i64>,
+    on_soft_limit: Option<SoftLimitCallback>,
+}
+
+impl CreditBudget {
+    /// A budget with no limits, which only tracks usage
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run the soft-limit callback once `credits` have been spent
+    pub fn soft_limit(mut self, credits: i64) -> Self {
+        self.soft_limit = Some(credits);
+        self
+    }
+
+    /// Refuse to send requests once `credits` have been spent
+    pub fn hard_limit(mut self, credits: i64) -> Self {
+        self.hard_limit = Some(credits);
+        self
+    }
+
+    /// Call `callback` with the usage so far when the soft limit is first reached
+    pub fn on_soft_limit(
+        mut self,
+        callback: impl Fn(&CreditUsage) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_soft_limit = Some(Arc::new(callback));
+        self
+    }
+}
+
+impl fmt::Debug for CreditBudget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CreditBudget")
+            .field("soft_limit", &self.soft_limit)
+            .field("hard_limit", &self.hard_limit)
+            .field("on_soft_limit", &self.on_soft_limit.is_some())
+            .finish()
+    }
+}
+
+/// Credits spent so far
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CreditUsage {
+    pub total: i64,
+    /// Credits by endpoint path, such as `"/enc"`
+    pub by_endpoint: BTreeMap<String, i64>,
+    /// Requests that reported a credit count
+    pub requests: u64,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    usage: CreditUsage,
+    soft_limit_reached: bool,
+}
+
+/// Tracks credits reported by responses and enforces a [`CreditBudget`].
+///
+/// Scopes made with [`scoped`](Self::scoped) count their own usage and also charge
+/// every enclosing scope, so a batch can have its own budget inside the SDK-wide one.
+/// The hard limit is checked before each request, so requests already in flight when
+/// it is reached can overshoot it by their cost.
+#[derive(Debug)]
+pub struct CreditAccountant {
+    budget: CreditBudget,
+    state: Mutex<State>,
+    parent: Option<Arc<CreditAccountant>>,
+}
+
+impl CreditAccountant {
+    /// Start tracking from zero under `budget`
+    pub fn new(budget: CreditBudget) -> Self {
+        Self {
+            budget,
+            state: Mutex::new(State::default()),
+            parent: None,
+        }
+    }
+
+    /// A child scope with its own `budget` that also charges this accountant
+    pub fn scoped(self: &Arc<Self>, budget: CreditBudget) -> Arc<Self> {
+        Arc::new(Self {
+            budget,
+            state: Mutex::new(State::default()),
+            parent: Some(self.clone()),
+        })
+    }
+
+    /// The budget enforced by this scope
+    pub fn budget(&self) -> &CreditBudget {
+        &self.budget
+    }
+
+    /// Credits spent in this scope
+    pub fn usage(&self) -> CreditUsage {
+        self.lock().usage.clone()
+    }
+
+    /// Total credits spent in this scope
+    pub fn spent(&self) -> i64 {
+        self.lock().usage.total
+    }
+
+    /// Credits left before the hard limit of this scope or any enclosing one, if there is one
+    pub fn remaining(&self) -> Option<i64> {
+        let own = self
+            .budget
+            .hard_limit
+            .map(|limit| (limit - self.spent()).max(0));
+        let parent = self.parent.as_ref().and_then(|parent| parent.remaining());
+        match (own, parent) {
+            (Some(own), Some(parent)) => Some(own.min(parent)),
+            (own, parent) => own.or(parent),
+        }
+    }
+
+    /// Fail with [`CufinderError::BudgetExceeded`] if this scope or an enclosing one has
+    /// reached its hard limit
+    pub fn check(&self) -> Result<()> {
+        if let Some(limit) = self.budget.hard_limit {
+            let spent = self.spent();
+            if spent >= limit {
+                return Err(CufinderError::BudgetExceeded { spent, limit });
+            }
+        }
+        match &self.parent {
+            Some(parent) => parent.check(),
+            None => Ok(()),
+        }
+    }
+
+    /// Charge `credits` spent on `endpoint` to this scope and every enclosing one
+    pub fn record(&self, endpoint: &str, credits: i64) {
+        let reached = {
+            let mut state = self.lock();
+            state.usage.total += credits;
+            state.usage.requests += 1;
+            *state
+                .usage
+                .by_endpoint
+                .entry(endpoint.to_string())
+                .or_default() += credits;
+
+            let crossed = self
+                .budget
+                .soft_limit
+                .is_some_and(|limit| state.usage.total >= limit);
+            if crossed && !state.soft_limit_reached {
+                state.soft_limit_reached = true;
+                Some(state.usage.clone())
+            } else {
+                None
+            }
+        };
+
+        // Run the callback without holding the lock, so it may inspect the accountant
+        if let (Some(usage), Some(callback)) = (reached, &self.budget.on_soft_limit) {
+            callback(&usage);
+        }
+        if let Some(parent) = &self.parent {
+            parent.record(endpoint, credits);
+        }
+    }
+
+    /// Forget the usage of this scope, re-arming the soft limit. Enclosing scopes keep theirs.
+    pub fn reset(&self) {
+        *self.lock() = State::default();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for CreditAccountant {
+    fn default() -> Self {
+        Self::new(CreditBudget::default())
+    }
+}
@@ -0,0 +1,363 @@
+//! Date parsing for profile, posting and founding dates
+//!
+//! [`PartialDate`] is always available. The typed accessors built on the `time` crate, such
+//! as `posted_at()` and `founded()`, require the `time` feature.
+
+#[cfg(feature = "time")]
+use crate::types::{CaaActivity, CaaResponse, CjaCompany, CjaJob, CjaJobItem, CjaResponse, EncCompany, FclCompany};
+#[cfg(feature = "time")]
+use std::cmp::Reverse;
+#[cfg(feature = "time")]
+use time::{
+    Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, format_description::well_known::Rfc3339,
+    macros::format_description,
+};
+
+/// Year with optional month, as found in profile start and end dates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PartialDate {
+    pub year: i32,
+    pub month: Option<u32>,
+}
+
+const MONTH_NAMES: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+impl PartialDate {
+    /// Parse dates such as `2020`, `2020-03`, `2020-03-15`, `03/2020`, `Mar 2020` or `March 2020`.
+    /// Returns `None` for `Present` and anything unrecognized.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let year_of = |s: &str| s.parse::<i32>().ok().filter(|y| (1900..=9999).contains(y));
+        let month_of = |s: &str| s.parse::<u32>().ok().filter(|m| (1..=12).contains(m));
+
+        // ISO style: 2020, 2020-03, 2020-03-15, 2020-03-15T10:00:00Z
+        if let Some((year, month, _)) = iso_parts(value) {
+            let year = year_of(year)?;
+            return match month {
+                None => Some(Self { year, month: None }),
+                Some(m) => month_of(m).map(|month| Self { year, month: Some(month) }),
+            };
+        }
+
+        // 03/2020 or 15/03/2020
+        let parts: Vec<&str> = value.split('/').collect();
+        if parts.len() >= 2 {
+            let year = year_of(parts[parts.len() - 1])?;
+            let month = month_of(parts[parts.len() - 2])?;
+            return Some(Self { year, month: Some(month) });
+        }
+
+        // Mar 2020, March 2020
+        let mut words = value.split_whitespace();
+        let (name, year) = (words.next()?, words.next()?);
+        let name = name.to_ascii_lowercase();
+        let month = MONTH_NAMES.iter().position(|m| name.starts_with(m))? as u32 + 1;
+        Some(Self { year: year_of(year)?, month: Some(month) })
+    }
+
+    /// Current UTC year and month
+    pub fn today() -> Self {
+        let secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        let (year, month, _) = civil_from_days(secs.div_euclid(86_400));
+        Self { year, month: Some(month) }
+    }
+
+    /// Months since year 0, treating a missing month as January
+    pub(crate) fn month_index(&self) -> i32 {
+        self.year * 12 + self.month.unwrap_or(1) as i32 - 1
+    }
+
+    /// Whole months from `self` to `other`, or zero if `other` is earlier
+    pub fn months_until(&self, other: &PartialDate) -> u32 {
+        (other.month_index() - self.month_index()).max(0) as u32
+    }
+}
+
+/// Convert days since the Unix epoch to a (year, month, day) civil date
+pub(crate) fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = (yoe + era * 400 + i64::from(month <= 2)) as i32;
+    (year, month, day)
+}
+
+/// Split an ISO style date such as `2015`, `2015-06`, `2015-06-01` or `2015-06-01T10:00:00Z`
+/// into its year, month and day, ignoring any time of day
+fn iso_parts(value: &str) -> Option<(&str, Option<&str>, Option<&str>)> {
+    let value = value.trim();
+    let value = value.split(['T', ' ']).next().unwrap_or(value);
+    let mut parts = value.split('-');
+
+    let year = parts.next().filter(|y| y.len() == 4 && y.bytes().all(|b| b.is_ascii_digit()))?;
+    let (month, day) = (parts.next(), parts.next());
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((year, month, day))
+}
+
+/// Parse an absolute date or timestamp as returned by the API.
+///
+/// Accepts RFC 3339, `YYYY-MM-DD HH:MM:SS`, `YYYY-MM-DD`, `YYYY-MM`, a bare year and
+/// Unix timestamps in seconds or milliseconds. Values without an offset are taken as UTC.
+#[cfg(feature = "time")]
+pub fn parse_datetime(value: &str) -> Option<OffsetDateTime> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+
+    if value.bytes().all(|b| b.is_ascii_digit()) {
+        return match value.len() {
+            4 => parse_date(value).map(|d| d.midnight().assume_utc()),
+            10 => OffsetDateTime::from_unix_timestamp(value.parse().ok()?).ok(),
+            13 => OffsetDateTime::from_unix_timestamp_nanos(value.parse::<i128>().ok()? * 1_000_000).ok(),
+            _ => None,
+        };
+    }
+
+    if let Ok(datetime) = OffsetDateTime::parse(value, &Rfc3339) {
+        return Some(datetime);
+    }
+    let naive = format_description!("[year]-[month]-[day] [hour]:[minute]:[second]");
+    let naive_t = format_description!("[year]-[month]-[day]T[hour]:[minute]:[second]");
+    if let Ok(datetime) = PrimitiveDateTime::parse(value, naive).or_else(|_| PrimitiveDateTime::parse(value, naive_t)) {
+        return Some(datetime.assume_utc());
+    }
+
+    parse_date(value).map(|d| d.midnight().assume_utc())
+}
+
+/// Parse a calendar date such as `2015-06-01`, `2015-06` or `2015`.
+/// Missing month and day default to the first.
+#[cfg(feature = "time")]
+pub fn parse_date(value: &str) -> Option<Date> {
+    let (year, month, day) = iso_parts(value)?;
+    let year: i32 = year.parse().ok()?;
+    let month: u8 = month.map_or(Some(1), |m| m.parse().ok())?;
+    let day: u8 = day.map_or(Some(1), |d| d.parse().ok())?;
+
+    Date::from_calendar_date(year, Month::try_from(month).ok()?, day).ok()
+}
+
+/// Parse relative text such as `3 days ago`, `an hour ago`, `30+ days ago`, `2w` or
+/// `yesterday`, anchored to `reference`. Months count as 30 days and years as 365 days.
+#[cfg(feature = "time")]
+pub fn parse_relative(text: &str, reference: OffsetDateTime) -> Option<OffsetDateTime> {
+    let text: String = text
+        .to_ascii_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { ' ' })
+        .collect();
+    let tokens: Vec<&str> = text
+        .split_whitespace()
+        .filter(|t| !matches!(*t, "posted" | "reposted" | "about" | "over" | "ago"))
+        .collect();
+
+    match tokens.as_slice() {
+        [] => return None,
+        ["just", "now"] | ["now"] | ["today"] | ["moments"] => return Some(reference),
+        ["yesterday"] => return Some(reference - Duration::DAY),
+        _ => {}
+    }
+
+    let (amount, unit) = match tokens.as_slice() {
+        [first, rest @ ..] => {
+            let digits = first.len() - first.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            if digits > 0 && digits < first.len() {
+                // Compact form: 3d, 2mo, 1yr
+                (first[..digits].parse::<i64>().ok()?, &first[digits..])
+            } else {
+                let amount = match *first {
+                    "a" | "an" | "one" => 1,
+                    n => n.parse::<i64>().ok()?,
+                };
+                (amount, *rest.first()?)
+            }
+        }
+        [] => return None,
+    };
+
+    let unit = match unit {
+        "s" | "sec" | "secs" | "second" | "seconds" => Duration::SECOND,
+        "m" | "min" | "mins" | "minute" | "minutes" => Duration::MINUTE,
+        "h" | "hr" | "hrs" | "hour" | "hours" => Duration::HOUR,
+        "d" | "day" | "days" => Duration::DAY,
+        "w" | "wk" | "wks" | "week" | "weeks" => Duration::WEEK,
+        "mo" | "mos" | "month" | "months" => Duration::days(30),
+        "y" | "yr" | "yrs" | "year" | "years" => Duration::days(365),
+        _ => return None,
+    };
+
+    reference.checked_sub(unit.checked_mul(i32::try_from(amount).ok()?)?)
+}
+
+#[cfg(feature = "time")]
+impl CaaActivity {
+    /// Parsed `activity_posted_at`, if it is an absolute date
+    pub fn posted_at(&self) -> Option<OffsetDateTime> {
+        self.activity_posted_at.as_deref().and_then(parse_datetime)
+    }
+
+    /// Parsed `activity_posted_at`, resolving relative text against `reference`
+    pub fn posted_at_relative_to(&self, reference: OffsetDateTime) -> Option<OffsetDateTime> {
+        let value = self.activity_posted_at.as_deref()?;
+        parse_datetime(value).or_else(|| parse_relative(value, reference))
+    }
+}
+
+#[cfg(feature = "time")]
+impl CjaJob {
+    /// Parsed `posted_at`
+    pub fn posted_at(&self) -> Option<OffsetDateTime> {
+        self.posted_at.as_deref().and_then(parse_datetime)
+    }
+
+    /// Parsed `posted_at`, falling back to `posted_at_text` resolved against `reference`
+    pub fn posted_at_relative_to(&self, reference: OffsetDateTime) -> Option<OffsetDateTime> {
+        self.posted_at().or_else(|| {
+            self.posted_at_text
+                .as_deref()
+                .and_then(|text| parse_relative(text, reference))
+        })
+    }
+}
+
+#[cfg(feature = "time")]
+impl CjaCompany {
+    /// Parsed `founded_date`
+    pub fn founded(&self) -> Option<Date> {
+        self.founded_date.as_deref().and_then(parse_date)
+    }
+}
+
+#[cfg(feature = "time")]
+impl FclCompany {
+    /// Parsed `founded_year`, as the first day of that year
+    pub fn founded(&self) -> Option<Date> {
+        self.founded_year.as_deref().and_then(parse_date)
+    }
+}
+
+#[cfg(feature = "time")]
+impl EncCompany {
+    /// Parsed `founded_year`, as the first day of that year
+    pub fn founded(&self) -> Option<Date> {
+        self.founded_year.as_deref().and_then(parse_date)
+    }
+}
+
+#[cfg(feature = "time")]
+impl CaaResponse {
+    /// Sort activities newest first, with undated activities last.
+    /// Relative posting text is resolved against the current time.
+    pub fn sort_by_posted_at(&mut self) {
+        self.sort_by_posted_at_relative_to(OffsetDateTime::now_utc());
+    }
+
+    /// Sort activities newest first, with undated activities last, resolving relative posting
+    /// text against `reference`
+    pub fn sort_by_posted_at_relative_to(&mut self, reference: OffsetDateTime) {
+        self.activities
+            .sort_by_cached_key(|activity| Reverse(activity.posted_at_relative_to(reference)));
+    }
+
+    /// Activities posted at or after `since`, resolving relative posting text against the current time
+    pub fn posted_since(&self, since: OffsetDateTime) -> Vec<&CaaActivity> {
+        self.posted_since_relative_to(since, OffsetDateTime::now_utc())
+    }
+
+    /// Activities posted at or after `since`, resolving relative posting text against `reference`
+    pub fn posted_since_relative_to(&self, since: OffsetDateTime, reference: OffsetDateTime) -> Vec<&CaaActivity> {
+        self.activities
+            .iter()
+            .filter(|activity| activity.posted_at_relative_to(reference).is_some_and(|posted| posted >= since))
+            .collect()
+    }
+
+    /// Activities posted between `start` and `end`, inclusive, resolving relative posting text
+    /// against the current time
+    pub fn posted_between(&self, start: OffsetDateTime, end: OffsetDateTime) -> Vec<&CaaActivity> {
+        self.posted_between_relative_to(start, end, OffsetDateTime::now_utc())
+    }
+
+    /// Activities posted between `start` and `end`, inclusive, resolving relative posting text
+    /// against `reference`
+    pub fn posted_between_relative_to(
+        &self,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+        reference: OffsetDateTime,
+    ) -> Vec<&CaaActivity> {
+        self.activities
+            .iter()
+            .filter(|activity| {
+                activity.posted_at_relative_to(reference)
+                    .is_some_and(|posted| posted >= start && posted <= end)
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "time")]
+impl CjaResponse {
+    /// Sort jobs newest first, with undated jobs last.
+    /// Relative posting text is resolved against the current time.
+    pub fn sort_by_posted_at(&mut self) {
+        self.sort_by_posted_at_relative_to(OffsetDateTime::now_utc());
+    }
+
+    /// Sort jobs newest first, with undated jobs last, resolving relative posting
+    /// text against `reference`
+    pub fn sort_by_posted_at_relative_to(&mut self, reference: OffsetDateTime) {
+        self.jobs
+            .sort_by_cached_key(|item| Reverse(item.job.posted_at_relative_to(reference)));
+    }
+
+    /// Jobs posted at or after `since`, resolving relative posting text against the current time
+    pub fn posted_since(&self, since: OffsetDateTime) -> Vec<&CjaJobItem> {
+        self.posted_since_relative_to(since, OffsetDateTime::now_utc())
+    }
+
+    /// Jobs posted at or after `since`, resolving relative posting text against `reference`
+    pub fn posted_since_relative_to(&self, since: OffsetDateTime, reference: OffsetDateTime) -> Vec<&CjaJobItem> {
+        self.jobs
+            .iter()
+            .filter(|item| item.job.posted_at_relative_to(reference).is_some_and(|posted| posted >= since))
+            .collect()
+    }
+
+    /// Jobs posted between `start` and `end`, inclusive, resolving relative posting text
+    /// against the current time
+    pub fn posted_between(&self, start: OffsetDateTime, end: OffsetDateTime) -> Vec<&CjaJobItem> {
+        self.posted_between_relative_to(start, end, OffsetDateTime::now_utc())
+    }
+
+    /// Jobs posted between `start` and `end`, inclusive, resolving relative posting text
+    /// against `reference`
+    pub fn posted_between_relative_to(
+        &self,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+        reference: OffsetDateTime,
+    ) -> Vec<&CjaJobItem> {
+        self.jobs
+            .iter()
+            .filter(|item| {
+                item.job.posted_at_relative_to(reference)
+                    .is_some_and(|posted| posted >= start && posted <= end)
+            })
+            .collect()
+    }
+}
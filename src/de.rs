@@ -0,0 +1,90 @@
+//! Lenient deserializers for loosely typed API fields
+
+use serde::{Deserialize, Deserializer, de::DeserializeOwned};
+use serde_json::Value;
+
+/// Accept a string, number, boolean or `{ "name": .. }` object as an optional string.
+/// Empty strings become `None`.
+pub(crate) fn lenient_string<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Option::<Value>::deserialize(deserializer)?;
+    Ok(value.and_then(value_to_string))
+}
+
+fn value_to_string(value: Value) -> Option<String> {
+    match value {
+        Value::String(s) => {
+            let trimmed = s.trim();
+            if trimmed.is_empty() {
+                None
+            } else {
+                Some(trimmed.to_string())
+            }
+        }
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Object(mut map) => {
+            // Dates are sometimes sent as { "year": 2020, "month": 1 }
+            if let Some(year) = map.get("year").and_then(Value::as_i64) {
+                return Some(match map.get("month").and_then(Value::as_i64) {
+                    Some(month) => format!("{:04}-{:02}", year, month),
+                    None => format!("{:04}", year),
+                });
+            }
+            map.remove("name").and_then(value_to_string)
+        }
+        Value::Null | Value::Array(_) => None,
+    }
+}
+
+/// Accept a boolean, `"true"`/`"yes"`/`"1"` style strings or a number as an optional bool.
+pub(crate) fn lenient_bool<'de, D>(deserializer: D) -> Result<Option<bool>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Option::<Value>::deserialize(deserializer)?;
+    Ok(match value {
+        Some(Value::Bool(b)) => Some(b),
+        Some(Value::Number(n)) => n.as_f64().map(|n| n != 0.0),
+        Some(Value::String(s)) => match s.trim().to_ascii_lowercase().as_str() {
+            "true" | "yes" | "y" | "1" => Some(true),
+            "false" | "no" | "n" | "0" => Some(false),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+/// Read a number or a string such as `"500+"` or `"1,200"` as a count.
+pub(crate) fn count(value: &Value) -> Option<i32> {
+    match value {
+        Value::Number(n) => n.as_i64().and_then(|n| i32::try_from(n).ok()),
+        Value::String(s) => {
+            let digits: String = s.chars().filter(char::is_ascii_digit).collect();
+            digits.parse().ok()
+        }
+        _ => None,
+    }
+}
+
+/// Accept an array, a single object or null as an optional list.
+/// Entries that cannot be parsed are skipped instead of failing the whole response.
+pub(crate) fn lenient_vec<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let value = Option::<Value>::deserialize(deserializer)?;
+    Ok(match value {
+        Some(Value::Array(items)) => Some(
+            items
+                .into_iter()
+                .filter_map(|item| serde_json::from_value(item).ok())
+                .collect(),
+        ),
+        Some(item @ Value::Object(_)) => Some(serde_json::from_value(item).ok().into_iter().collect()),
+        _ => None,
+    })
+}
@@ -0,0 +1,160 @@
+//! The [`Endpoint`] trait and one implementor per CUFinder API endpoint
+
+use crate::{
+    envelope::ApiEnvelope,
+    error::{CufinderError, Result},
+    types::*,
+};
+use serde::{Serialize, de::DeserializeOwned};
+
+/// One API endpoint: its path, parameters, response and validation.
+///
+/// Call any endpoint with [`Service::call`](crate::services::Service::call) or
+/// [`CufinderSDK::call`](crate::CufinderSDK::call):
+///
+/// ```no_run
+/// use cufinder_rust::{CufinderSDK, DtcParams, endpoint::Dtc};
+///
+/// # async fn run(sdk: CufinderSDK) -> cufinder_rust::Result<()> {
+/// let response = sdk
+///     .call::<Dtc>(DtcParams {
+///         company_website: "stripe.com".to_string(),
+///     })
+///     .await?;
+/// println!("{}", response.company_name);
+/// # Ok(())
+/// # }
+/// ```
+pub trait Endpoint {
+    /// Form parameters sent to the endpoint
+    type Params: Serialize + Send + Sync;
+    /// Response body returned by the endpoint
+    type Response: DeserializeOwned + Send;
+
+    /// Path of the endpoint, such as `"/enc"`
+    const PATH: &'static str;
+
+    /// Reject parameters the API would refuse, before sending them
+    fn validate(_params: &Self::Params) -> Result<()> {
+        Ok(())
+    }
+
+    /// Build the response from its envelope; by default the envelope fields are dropped
+    fn from_envelope(envelope: ApiEnvelope<Self::Response>) -> Self::Response {
+        envelope.data
+    }
+}
+
+macro_rules! endpoints {
+    ($(
+        $(#[doc = $doc:literal])*
+        $name:ident($path:literal, $params:ty => $response:ty) $(requires $($field:ident),+)? $(checked by $check:path)?;
+    )*) => {
+        $(
+            $(#[doc = $doc])*
+            #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+            pub struct $name;
+
+            impl Endpoint for $name {
+                type Params = $params;
+                type Response = $response;
+
+                const PATH: &'static str = $path;
+
+                fn from_envelope(envelope: ApiEnvelope<$response>) -> $response {
+                    let mut response = envelope.data;
+                    if envelope.meta_data.is_some() {
+                        response.base.meta_data = envelope.meta_data;
+                    }
+                    response
+                }
+
+                $(
+                    fn validate(params: &Self::Params) -> Result<()> {
+                        $(
+                            if params.$field.is_empty() {
+                                return Err(CufinderError::ValidationError(
+                                    concat!(stringify!($field), " is required").to_string(),
+                                ));
+                            }
+                        )+
+                        Ok(())
+                    }
+                )?
+
+                $(
+                    fn validate(params: &Self::Params) -> Result<()> {
+                        $check(params)
+                    }
+                )?
+            }
+        )*
+    };
+}
+
+endpoints! {
+    /// CUF - Company URL Finder
+    Cuf("/cuf", CufParams => CufResponse) requires company_name, country_code;
+    /// LCUF - LinkedIn Company URL Finder
+    Lcuf("/lcuf", LcufParams => LcufResponse) requires company_name;
+    /// DTC - Domain to Company
+    Dtc("/dtc", DtcParams => DtcResponse) requires company_website;
+    /// DTE - Domain to Emails
+    Dte("/dte", DteParams => DteResponse) requires company_website;
+    /// NTP - Name to Phones
+    Ntp("/ntp", NtpParams => NtpResponse) requires company_name;
+    /// REL - Reverse Email Lookup
+    Rel("/rel", RelParams => RelResponse) requires email;
+    /// FCL - Find Company Lookalikes
+    Fcl("/fcl", FclParams => FclResponse) requires query;
+    /// ELF - Enrich LinkedIn Fundraising
+    Elf("/elf", ElfParams => ElfResponse) requires query;
+    /// CAR - Company Annual Revenue
+    Car("/car", CarParams => CarResponse) requires query;
+    /// FCC - Find Company Children
+    Fcc("/fcc", FccParams => FccResponse) requires query;
+    /// FTS - Find Tech Stack
+    Fts("/fts", FtsParams => FtsResponse) requires query;
+    /// EPP - Enrich Profile
+    Epp("/epp", EppParams => EppResponse) requires linkedin_url;
+    /// FWE - Find Work Email
+    Fwe("/fwe", FweParams => FweResponse) requires linkedin_url;
+    /// TEP - Person Enrichment
+    Tep("/tep", TepParams => TepResponse) requires full_name, company;
+    /// ENC - Company Enrichment
+    Enc("/enc", EncParams => EncResponse) requires query;
+    /// CEC - Company Employee Countries
+    Cec("/cec", CecParams => CecResponse) requires query;
+    /// CLO - Company Locations
+    Clo("/clo", CloParams => CloResponse) requires query;
+    /// CSE - Company Search
+    Cse("/cse", CseParams => CseResponse) checked by CseParams::validate;
+    /// PSE - Person Search
+    Pse("/pse", PseParams => PseResponse) checked by PseParams::validate;
+    /// LBS - Local Business Search
+    Lbs("/lbs", LbsParams => LbsResponse) checked by LbsParams::validate;
+    /// BCD - B2B Customers Finder
+    Bcd("/bcd", BcdParams => BcdResponse) requires url;
+    /// CCP - Company Career Page Finder
+    Ccp("/ccp", CcpParams => CcpResponse) requires url;
+    /// ISC - Company Saas Checker
+    Isc("/isc", IscParams => IscResponse) requires url;
+    /// CBC - Company B2B or B2C Checker
+    Cbc("/cbc", CbcParams => CbcResponse) requires url;
+    /// CSC - Company Mission Statement
+    Csc("/csc", CscParams => CscResponse) requires url;
+    /// CSN - Company Snapshot
+    Csn("/csn", CsnParams => CsnResponse) requires url;
+    /// NAO - Phone Number Normalizer
+    Nao("/nao", NaoParams => NaoResponse) requires phone;
+    /// NAA - Address Normalizer
+    Naa("/naa", NaaParams => NaaResponse) requires address;
+    /// CEF - Company Employee Finder
+    Cef("/cef", CefParams => CefResponse) requires query;
+    /// NAC - Normalize Company Name
+    Nac("/nac", NacParams => NacResponse) requires company;
+    /// CAA - Company Activity API
+    Caa("/caa", CaaParams => CaaResponse) requires query;
+    /// CJA - Company Jobs API
+    Cja("/cja", CjaParams => CjaResponse) checked by CjaParams::validate;
+}
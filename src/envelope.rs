@@ -0,0 +1,438 @@
+//! Typed decoding of `{"data": ..., "meta_data": ...}` response envelopes
+
+use crate::error::Result;
+use serde::{
+    Deserialize, Deserializer, Serialize,
+    de::{
+        DeserializeOwned, DeserializeSeed, EnumAccess, Error as _, IgnoredAny, MapAccess,
+        SeqAccess, Visitor,
+        value::{BorrowedStrDeserializer, StringDeserializer},
+    },
+};
+use serde_json::Value;
+use std::{fmt, sync::Arc};
+
+/// A response body as sent by the API, with the payload under `data`
+///
+/// ```
+/// use cufinder_rust::envelope::ApiEnvelope;
+///
+/// let body = r#"{"status": 1, "data": [1, 2, 3], "meta_data": {"page": 1}}"#;
+/// let envelope = ApiEnvelope::<Vec<u32>>::from_body(body).unwrap();
+/// assert_eq!(envelope.data, vec![1, 2, 3]);
+/// assert_eq!(envelope.meta_data.unwrap()["page"], 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiEnvelope<T> {
+    /// Status reported by the API, if any
+    #[serde(default)]
+    pub status: Option<Value>,
+    pub data: T,
+    /// Metadata sent beside `data`, such as query details
+    #[serde(default)]
+    pub meta_data: Option<Value>,
+}
+
+impl<T: DeserializeOwned> ApiEnvelope<T> {
+    /// Decode a response body in one pass. A body without a `data` key is decoded as the
+    /// payload itself, with no envelope fields.
+    pub fn from_body(body: &str) -> Result<Self> {
+        let envelope: ApiEnvelope<Option<T>> = serde_json::from_str(body)?;
+        match envelope.data {
+            Some(data) => Ok(Self {
+                status: envelope.status,
+                data,
+                meta_data: envelope.meta_data,
+            }),
+            None => Ok(Self::bare(serde_json::from_str(body)?)),
+        }
+    }
+
+    /// An envelope around an already unwrapped payload
+    pub fn bare(data: T) -> Self {
+        Self {
+            status: None,
+            data,
+            meta_data: None,
+        }
+    }
+}
+
+impl ApiEnvelope<Value> {
+    /// The payload with `meta_data` moved into it, as [`Client::post`](crate::Client::post)
+    /// returns it. An array or scalar payload is returned as is.
+    pub fn into_merged(self) -> Value {
+        match (self.data, self.meta_data) {
+            (Value::Object(mut data), Some(meta_data)) => {
+                data.insert("meta_data".to_string(), meta_data);
+                Value::Object(data)
+            }
+            (data, _) => data,
+        }
+    }
+}
+
+/// Credits and confidence of a response, read without decoding the rest of it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct Usage {
+    pub(crate) credit_count: Option<i64>,
+    pub(crate) confidence_level: Option<i64>,
+}
+
+impl Usage {
+    /// Read the usage of an encoded response body
+    pub(crate) fn from_body(body: &str) -> Result<Self> {
+        let envelope: ApiEnvelope<Option<Usage>> = serde_json::from_str(body)?;
+        match envelope.data {
+            Some(usage) => Ok(usage),
+            None => Ok(serde_json::from_str(body)?),
+        }
+    }
+
+    /// Decode an encoded response body as `T`, reading its usage in the same pass
+    pub(crate) fn decode<T: DeserializeOwned>(body: &str) -> Result<(ApiEnvelope<T>, Self)> {
+        let envelope: ApiEnvelope<Option<Tapped<T>>> = serde_json::from_str(body)?;
+        let (data, usage) = match envelope.data {
+            Some(Tapped(data, usage)) => (data, usage),
+            None => {
+                let Tapped(data, usage) = serde_json::from_str(body)?;
+                return Ok((ApiEnvelope::bare(data), usage));
+            }
+        };
+        let envelope = ApiEnvelope {
+            status: envelope.status,
+            data,
+            meta_data: envelope.meta_data,
+        };
+        Ok((envelope, usage))
+    }
+
+    /// Read the usage of an unwrapped response
+    pub(crate) fn from_value(value: &Value) -> Self {
+        let field = |name: &str| value.get(name).and_then(Value::as_i64);
+        Self {
+            credit_count: field("credit_count"),
+            confidence_level: field("confidence_level"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Usage {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        deserializer.deserialize_any(UsageVisitor)
+    }
+}
+
+/// Picks the two usage fields out of an object and skips everything else, including
+/// payloads that are not objects
+struct UsageVisitor;
+
+impl<'de> Visitor<'de> for UsageVisitor {
+    type Value = Usage;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a response payload")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> std::result::Result<Usage, A::Error> {
+        let mut usage = Usage::default();
+        while let Some(key) = map.next_key::<std::borrow::Cow<'de, str>>()? {
+            match key.as_ref() {
+                "credit_count" => usage.credit_count = map.next_value::<Value>()?.as_i64(),
+                "confidence_level" => usage.confidence_level = map.next_value::<Value>()?.as_i64(),
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(usage)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<Usage, A::Error> {
+        while seq.next_element::<IgnoredAny>()?.is_some() {}
+        Ok(Usage::default())
+    }
+
+    fn visit_bool<E>(self, _: bool) -> std::result::Result<Usage, E> {
+        Ok(Usage::default())
+    }
+
+    fn visit_i64<E>(self, _: i64) -> std::result::Result<Usage, E> {
+        Ok(Usage::default())
+    }
+
+    fn visit_u64<E>(self, _: u64) -> std::result::Result<Usage, E> {
+        Ok(Usage::default())
+    }
+
+    fn visit_f64<E>(self, _: f64) -> std::result::Result<Usage, E> {
+        Ok(Usage::default())
+    }
+
+    fn visit_str<E>(self, _: &str) -> std::result::Result<Usage, E> {
+        Ok(Usage::default())
+    }
+
+    fn visit_unit<E>(self) -> std::result::Result<Usage, E> {
+        Ok(Usage::default())
+    }
+}
+
+/// A payload decoded as `T` along with the usage fields of its top-level object
+struct Tapped<T>(T, Usage);
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Tapped<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let mut usage = Usage::default();
+        let data = T::deserialize(UsageTap {
+            inner: deserializer,
+            usage: &mut usage,
+        })?;
+        Ok(Tapped(data, usage))
+    }
+}
+
+/// Passes a payload through to the deserializer of `T`, noting the usage fields of the
+/// first object it reaches on the way
+struct UsageTap<'u, D> {
+    inner: D,
+    usage: &'u mut Usage,
+}
+
+macro_rules! forward_tapped {
+    ($($method:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, $($arg: $ty,)* visitor: V) -> std::result::Result<V::Value, D::Error> {
+                self.inner.$method($($arg,)* TapVisitor { inner: visitor, usage: self.usage })
+            }
+        )*
+    };
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for UsageTap<'_, D> {
+    type Error = D::Error;
+
+    forward_tapped! {
+        deserialize_any();
+        deserialize_bool();
+        deserialize_i8();
+        deserialize_i16();
+        deserialize_i32();
+        deserialize_i64();
+        deserialize_i128();
+        deserialize_u8();
+        deserialize_u16();
+        deserialize_u32();
+        deserialize_u64();
+        deserialize_u128();
+        deserialize_f32();
+        deserialize_f64();
+        deserialize_char();
+        deserialize_str();
+        deserialize_string();
+        deserialize_bytes();
+        deserialize_byte_buf();
+        deserialize_option();
+        deserialize_unit();
+        deserialize_unit_struct(name: &'static str);
+        deserialize_newtype_struct(name: &'static str);
+        deserialize_seq();
+        deserialize_tuple(len: usize);
+        deserialize_tuple_struct(name: &'static str, len: usize);
+        deserialize_map();
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]);
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]);
+        deserialize_identifier();
+        deserialize_ignored_any();
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+/// Visitor of [`UsageTap`], which reads the usage fields out of the first map it visits
+struct TapVisitor<'u, V> {
+    inner: V,
+    usage: &'u mut Usage,
+}
+
+macro_rules! forward_visits {
+    ($($method:ident($ty:ty);)*) => {
+        $(
+            fn $method<E: serde::de::Error>(self, value: $ty) -> std::result::Result<V::Value, E> {
+                self.inner.$method(value)
+            }
+        )*
+    };
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for TapVisitor<'_, V> {
+    type Value = V::Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.expecting(f)
+    }
+
+    forward_visits! {
+        visit_bool(bool);
+        visit_i8(i8);
+        visit_i16(i16);
+        visit_i32(i32);
+        visit_i64(i64);
+        visit_i128(i128);
+        visit_u8(u8);
+        visit_u16(u16);
+        visit_u32(u32);
+        visit_u64(u64);
+        visit_u128(u128);
+        visit_f32(f32);
+        visit_f64(f64);
+        visit_char(char);
+        visit_str(&str);
+        visit_borrowed_str(&'de str);
+        visit_string(String);
+        visit_bytes(&[u8]);
+        visit_borrowed_bytes(&'de [u8]);
+        visit_byte_buf(Vec<u8>);
+    }
+
+    fn visit_none<E: serde::de::Error>(self) -> std::result::Result<V::Value, E> {
+        self.inner.visit_none()
+    }
+
+    fn visit_unit<E: serde::de::Error>(self) -> std::result::Result<V::Value, E> {
+        self.inner.visit_unit()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<V::Value, D::Error> {
+        self.inner.visit_some(UsageTap {
+            inner: deserializer,
+            usage: self.usage,
+        })
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<V::Value, D::Error> {
+        self.inner.visit_newtype_struct(UsageTap {
+            inner: deserializer,
+            usage: self.usage,
+        })
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> std::result::Result<V::Value, A::Error> {
+        self.inner.visit_seq(seq)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> std::result::Result<V::Value, A::Error> {
+        self.inner.visit_map(TapMap {
+            inner: map,
+            usage: self.usage,
+            field: None,
+        })
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> std::result::Result<V::Value, A::Error> {
+        self.inner.visit_enum(data)
+    }
+}
+
+/// Usage field whose value is up next in a [`TapMap`]
+#[derive(Clone, Copy)]
+enum UsageField {
+    CreditCount,
+    ConfidenceLevel,
+}
+
+/// Map access that hands every entry on unchanged, keeping a copy of the usage fields
+struct TapMap<'u, A> {
+    inner: A,
+    usage: &'u mut Usage,
+    field: Option<UsageField>,
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for TapMap<'_, A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> std::result::Result<Option<K::Value>, A::Error> {
+        let Some(key) = self.inner.next_key::<std::borrow::Cow<'de, str>>()? else {
+            return Ok(None);
+        };
+        self.field = match key.as_ref() {
+            "credit_count" => Some(UsageField::CreditCount),
+            "confidence_level" => Some(UsageField::ConfidenceLevel),
+            _ => None,
+        };
+        match key {
+            std::borrow::Cow::Borrowed(key) => seed.deserialize(BorrowedStrDeserializer::new(key)),
+            std::borrow::Cow::Owned(key) => seed.deserialize(StringDeserializer::new(key)),
+        }
+        .map(Some)
+    }
+
+    fn next_value_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> std::result::Result<S::Value, A::Error> {
+        let Some(field) = self.field.take() else {
+            return self.inner.next_value_seed(seed);
+        };
+        let value: Value = self.inner.next_value()?;
+        match field {
+            UsageField::CreditCount => self.usage.credit_count = value.as_i64(),
+            UsageField::ConfidenceLevel => self.usage.confidence_level = value.as_i64(),
+        }
+        seed.deserialize(value).map_err(A::Error::custom)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+/// A successful response on its way through the client, decoded only once its caller
+/// knows the type it wants
+#[derive(Debug, Clone)]
+pub(crate) enum ResponseBody {
+    /// Body received from the network, still encoded
+    Encoded(Arc<String>),
+    /// An unwrapped response, such as one answered from the cache
+    Decoded(Value),
+}
+
+impl ResponseBody {
+    /// Credits and confidence of the response, for bodies that were not decoded
+    pub(crate) fn usage(&self) -> Result<Usage> {
+        match self {
+            Self::Encoded(body) => Usage::from_body(body),
+            Self::Decoded(value) => Ok(Usage::from_value(value)),
+        }
+    }
+
+    /// Decode the envelope and its payload as `T`, along with the response's usage
+    pub(crate) fn decode<T: DeserializeOwned>(&self) -> Result<(ApiEnvelope<T>, Usage)> {
+        match self {
+            Self::Encoded(body) => Usage::decode(body),
+            Self::Decoded(value) => Ok((
+                ApiEnvelope::bare(T::deserialize(value)?),
+                Usage::from_value(value),
+            )),
+        }
+    }
+
+    /// The unwrapped response with `meta_data` merged into it
+    pub(crate) fn into_value(self) -> Result<Value> {
+        match self {
+            Self::Encoded(body) => Ok(ApiEnvelope::<Value>::from_body(&body)?.into_merged()),
+            Self::Decoded(value) => Ok(value),
+        }
+    }
+}
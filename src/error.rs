@@ -1,3 +1,5 @@
+use crate::client::PreparedRequest;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// CUFinder SDK error types
@@ -26,6 +28,22 @@ pub enum CufinderError {
     
     #[error("Network error: {0}")]
     NetworkError(String),
+
+    /// An [`HttpError`](Self::HttpError) shared with every caller of a coalesced request
+    #[error("HTTP request failed: {message}")]
+    SharedHttpError { message: String, timeout: bool, connect: bool },
+
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Not in cache: {0}")]
+    CacheMiss(String),
+
+    #[error("Credit budget exhausted: {spent} of {limit} credits spent")]
+    BudgetExceeded { spent: i64, limit: i64 },
+
+    #[error("Dry run, not sent: {0}")]
+    DryRun(Box<PreparedRequest>),
     
     #[error("Unknown error: {0}")]
     UnknownError(String),
@@ -33,3 +51,132 @@ pub enum CufinderError {
 
 /// Result type alias for CUFinder operations
 pub type Result<T> = std::result::Result<T, CufinderError>;
+
+/// Broad category of a [`CufinderError`], for grouping and counting failures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    Http,
+    Json,
+    Api,
+    Validation,
+    Authentication,
+    RateLimit,
+    CreditLimit,
+    Network,
+    Io,
+    CacheMiss,
+    Budget,
+    DryRun,
+    Unknown,
+}
+
+impl ErrorKind {
+    /// Short snake_case name, suitable for logs and metric labels
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Http => "http",
+            Self::Json => "json",
+            Self::Api => "api",
+            Self::Validation => "validation",
+            Self::Authentication => "authentication",
+            Self::RateLimit => "rate_limit",
+            Self::CreditLimit => "credit_limit",
+            Self::Network => "network",
+            Self::Io => "io",
+            Self::CacheMiss => "cache_miss",
+            Self::Budget => "budget",
+            Self::DryRun => "dry_run",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl CufinderError {
+    /// The category of this error
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::HttpError(_) => ErrorKind::Http,
+            Self::JsonError(_) => ErrorKind::Json,
+            Self::ApiError { .. } => ErrorKind::Api,
+            Self::ValidationError(_) => ErrorKind::Validation,
+            Self::AuthenticationError(_) => ErrorKind::Authentication,
+            Self::RateLimitError(_) => ErrorKind::RateLimit,
+            Self::CreditLimitError(_) => ErrorKind::CreditLimit,
+            Self::NetworkError(_) => ErrorKind::Network,
+            Self::SharedHttpError { .. } => ErrorKind::Http,
+            Self::IoError(_) => ErrorKind::Io,
+            Self::CacheMiss(_) => ErrorKind::CacheMiss,
+            Self::BudgetExceeded { .. } => ErrorKind::Budget,
+            Self::DryRun(_) => ErrorKind::DryRun,
+            Self::UnknownError(_) => ErrorKind::Unknown,
+        }
+    }
+
+    /// A copy of this error for sharing one failure between callers. Errors from
+    /// `reqwest`, which cannot be cloned, become [`SharedHttpError`](Self::SharedHttpError)s
+    /// that keep their kind, timeout and connection flags, so every caller classifies the
+    /// failure the same way.
+    pub(crate) fn duplicate(&self) -> Self {
+        match self {
+            Self::HttpError(e) => Self::SharedHttpError {
+                message: e.to_string(),
+                timeout: e.is_timeout(),
+                connect: e.is_connect(),
+            },
+            Self::SharedHttpError { message, timeout, connect } => Self::SharedHttpError {
+                message: message.clone(),
+                timeout: *timeout,
+                connect: *connect,
+            },
+            Self::JsonError(e) => Self::JsonError(serde::de::Error::custom(e)),
+            Self::ApiError { status, message } => Self::ApiError {
+                status: *status,
+                message: message.clone(),
+            },
+            Self::ValidationError(m) => Self::ValidationError(m.clone()),
+            Self::AuthenticationError(m) => Self::AuthenticationError(m.clone()),
+            Self::RateLimitError(m) => Self::RateLimitError(m.clone()),
+            Self::CreditLimitError(m) => Self::CreditLimitError(m.clone()),
+            Self::NetworkError(m) => Self::NetworkError(m.clone()),
+            Self::IoError(e) => Self::IoError(std::io::Error::new(e.kind(), e.to_string())),
+            Self::CacheMiss(m) => Self::CacheMiss(m.clone()),
+            Self::BudgetExceeded { spent, limit } => Self::BudgetExceeded {
+                spent: *spent,
+                limit: *limit,
+            },
+            Self::DryRun(request) => Self::DryRun(request.clone()),
+            Self::UnknownError(m) => Self::UnknownError(m.clone()),
+        }
+    }
+
+    /// Whether the request may succeed if sent again: rate limiting, server errors and
+    /// connection failures.
+    ///
+    /// Timeouts are not retryable: the API may have accepted and charged the request
+    /// before it timed out, so sending it again can charge twice. See [`is_timeout`](Self::is_timeout).
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::RateLimitError(_) | Self::NetworkError(_) => true,
+            Self::ApiError { status, .. } => *status >= 500,
+            Self::HttpError(e) => e.is_connect(),
+            Self::SharedHttpError { connect, .. } => *connect,
+            _ => false,
+        }
+    }
+
+    /// Whether the request timed out, possibly after the API received it
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            Self::HttpError(e) => e.is_timeout(),
+            Self::SharedHttpError { timeout, .. } => *timeout,
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Progress events for requests, paginated streams and batches
+
+use crate::error::ErrorKind;
+#[cfg(feature = "metrics")]
+use crate::metrics::MetricsRecorder;
+use std::{fmt, sync::Arc, time::Duration};
+
+/// Something that happened while the SDK was working
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// An HTTP request is about to be sent. `attempt` is 0 for the first try.
+    RequestStarted { endpoint: String, attempt: u32 },
+    /// An HTTP request finished, successfully if `error` is `None`
+    RequestFinished {
+        endpoint: String,
+        attempt: u32,
+        latency: Duration,
+        error: Option<ErrorKind>,
+    },
+    /// A failed request will be retried after `delay`
+    RetryScheduled {
+        endpoint: String,
+        attempt: u32,
+        delay: Duration,
+        error: ErrorKind,
+    },
+    /// A request was held back by the client-side rate limiter
+    RateLimitWait { endpoint: String, wait: Duration },
+    /// A successful response reported a credit cost
+    CreditsConsumed { endpoint: String, credits: i32 },
+    /// A call failed without a [`RequestFinished`](Event::RequestFinished) reporting the
+    /// error: it was refused before sending, or its response did not decode
+    CallFailed { endpoint: String, error: ErrorKind },
+    /// A response was served from the cache without a request
+    CacheHit { endpoint: String },
+    /// A request was answered by an identical request already in flight
+    RequestCoalesced { endpoint: String },
+    /// A paginated stream fetched a page
+    PageFetched {
+        page: i32,
+        items: usize,
+        total: Option<u64>,
+    },
+    /// A batch item succeeded. `index` is its position in the batch input.
+    ItemSucceeded { index: usize, credits: i32 },
+    /// A batch item failed
+    ItemFailed { index: usize, error: ErrorKind },
+    /// A call could not be written to the audit log
+    AuditWriteFailed {
+        endpoint: String,
+        error: ErrorKind,
+        message: String,
+    },
+}
+
+/// Receives [`Event`]s. Implemented for any `Fn(&Event) + Send + Sync` closure.
+///
+/// Observers are called inline on the task making the request, so they should return quickly.
+pub trait Observer: Send + Sync {
+    fn on_event(&self, event: &Event);
+}
+
+impl<F> Observer for F
+where
+    F: Fn(&Event) + Send + Sync,
+{
+    fn on_event(&self, event: &Event) {
+        self(event)
+    }
+}
+
+/// Optional shared observer and metrics recorder, cheap to clone into clients and streams
+#[derive(Clone, Default)]
+pub(crate) struct EventSink {
+    observer: Option<Arc<dyn Observer>>,
+    #[cfg(feature = "metrics")]
+    metrics: Option<Arc<dyn MetricsRecorder>>,
+}
+
+impl EventSink {
+    /// This sink reporting to `observer` instead of any previous one
+    pub(crate) fn with_observer(mut self, observer: Arc<dyn Observer>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// This sink recording metrics in `metrics` instead of any previous recorder
+    #[cfg(feature = "metrics")]
+    pub(crate) fn with_metrics(mut self, metrics: Arc<dyn MetricsRecorder>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Whether anything consumes events, so they are worth building
+    fn is_active(&self) -> bool {
+        #[cfg(feature = "metrics")]
+        if self.metrics.is_some() {
+            return true;
+        }
+        cfg!(feature = "tracing") || self.observer.is_some()
+    }
+
+    /// Send the event built by `event` to the observer, the metrics recorder and `tracing`,
+    /// whichever are present
+    pub(crate) fn emit(&self, event: impl FnOnce() -> Event) {
+        if !self.is_active() {
+            return;
+        }
+        let event = event();
+        #[cfg(feature = "tracing")]
+        crate::trace::on_event(&event);
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = &self.metrics {
+            crate::metrics::record_event(metrics.as_ref(), &event);
+        }
+        if let Some(observer) = &self.observer {
+            observer.on_event(&event);
+        }
+    }
+}
+
+impl fmt::Debug for EventSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("EventSink");
+        debug.field("observer", &self.observer.is_some());
+        #[cfg(feature = "metrics")]
+        debug.field("metrics", &self.metrics.is_some());
+        debug.finish()
+    }
+}
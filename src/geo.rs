@@ -0,0 +1,161 @@
+//! Geographic coordinates and distance helpers for company locations
+
+use crate::types::{CloLocation, CloResponse, MainLocation};
+
+/// Mean Earth radius used for haversine distances, in kilometers
+pub const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+const KM_PER_MILE: f64 = 1.609_344;
+
+/// A latitude/longitude pair in decimal degrees
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeoPoint {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl GeoPoint {
+    /// Create a point, returning `None` if the coordinates are out of range
+    pub fn new(latitude: f64, longitude: f64) -> Option<Self> {
+        let valid = (-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude);
+        valid.then_some(Self { latitude, longitude })
+    }
+
+    /// Parse `"lat,lng"`, `"lat, lng"` or `"lat lng"`
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut parts = value
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|p| !p.is_empty());
+        let latitude = parts.next()?.parse().ok()?;
+        let longitude = parts.next()?.parse().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        Self::new(latitude, longitude)
+    }
+
+    /// Parse separate latitude and longitude strings
+    pub fn from_strings(latitude: &str, longitude: &str) -> Option<Self> {
+        Self::new(latitude.trim().parse().ok()?, longitude.trim().parse().ok()?)
+    }
+
+    /// Great-circle distance to `other` in kilometers
+    pub fn distance_km(&self, other: &GeoPoint) -> f64 {
+        let (lat1, lat2) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let d_lat = lat2 - lat1;
+        let d_lon = (other.longitude - self.longitude).to_radians();
+
+        let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().min(1.0).asin()
+    }
+
+    /// Great-circle distance to `other` in miles
+    pub fn distance_miles(&self, other: &GeoPoint) -> f64 {
+        self.distance_km(other) / KM_PER_MILE
+    }
+}
+
+/// A latitude/longitude rectangle. Boxes crossing the antimeridian have `min_longitude > max_longitude`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub min_latitude: f64,
+    pub min_longitude: f64,
+    pub max_latitude: f64,
+    pub max_longitude: f64,
+}
+
+impl BoundingBox {
+    /// Create a box from its south-west and north-east corners
+    pub fn new(south_west: GeoPoint, north_east: GeoPoint) -> Self {
+        Self {
+            min_latitude: south_west.latitude,
+            min_longitude: south_west.longitude,
+            max_latitude: north_east.latitude,
+            max_longitude: north_east.longitude,
+        }
+    }
+
+    /// The smallest box containing every point within `radius_km` of `center`
+    pub fn around(center: GeoPoint, radius_km: f64) -> Self {
+        let d_lat = (radius_km / EARTH_RADIUS_KM).to_degrees();
+        let min_latitude = (center.latitude - d_lat).max(-90.0);
+        let max_latitude = (center.latitude + d_lat).min(90.0);
+
+        // Near the poles every longitude is within reach
+        if min_latitude <= -90.0 || max_latitude >= 90.0 {
+            return Self { min_latitude, min_longitude: -180.0, max_latitude, max_longitude: 180.0 };
+        }
+
+        let d_lon = (d_lat / center.latitude.to_radians().cos()).min(180.0);
+        let wrap = |lon: f64| ((lon + 540.0) % 360.0) - 180.0;
+        let (min_longitude, max_longitude) = if d_lon >= 180.0 {
+            (-180.0, 180.0)
+        } else {
+            (wrap(center.longitude - d_lon), wrap(center.longitude + d_lon))
+        };
+        Self { min_latitude, min_longitude, max_latitude, max_longitude }
+    }
+
+    /// Whether `point` lies inside the box, edges included
+    pub fn contains(&self, point: &GeoPoint) -> bool {
+        let in_latitude = (self.min_latitude..=self.max_latitude).contains(&point.latitude);
+        let in_longitude = if self.min_longitude <= self.max_longitude {
+            (self.min_longitude..=self.max_longitude).contains(&point.longitude)
+        } else {
+            point.longitude >= self.min_longitude || point.longitude <= self.max_longitude
+        };
+        in_latitude && in_longitude
+    }
+}
+
+/// The candidate closest to `origin`, with its distance in kilometers
+pub fn nearest<T>(origin: &GeoPoint, candidates: impl IntoIterator<Item = (T, GeoPoint)>) -> Option<(T, f64)> {
+    candidates
+        .into_iter()
+        .map(|(item, point)| (item, origin.distance_km(&point)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+impl CloLocation {
+    /// Parsed `latitude` and `longitude`
+    pub fn geo_point(&self) -> Option<GeoPoint> {
+        GeoPoint::from_strings(self.latitude.as_deref()?, self.longitude.as_deref()?)
+    }
+}
+
+impl MainLocation {
+    /// Parsed `geo` field
+    pub fn geo_point(&self) -> Option<GeoPoint> {
+        self.geo.as_deref().and_then(GeoPoint::parse)
+    }
+}
+
+impl CloResponse {
+    /// Locations with parseable coordinates
+    pub fn geo_locations(&self) -> impl Iterator<Item = (&CloLocation, GeoPoint)> {
+        self.locations
+            .iter()
+            .filter_map(|location| location.geo_point().map(|point| (location, point)))
+    }
+
+    /// Locations within `radius_km` of `center`
+    pub fn locations_within_km(&self, center: &GeoPoint, radius_km: f64) -> Vec<&CloLocation> {
+        self.geo_locations()
+            .filter(|(_, point)| center.distance_km(point) <= radius_km)
+            .map(|(location, _)| location)
+            .collect()
+    }
+
+    /// Locations inside `bounds`
+    pub fn locations_in(&self, bounds: &BoundingBox) -> Vec<&CloLocation> {
+        self.geo_locations()
+            .filter(|(_, point)| bounds.contains(point))
+            .map(|(location, _)| location)
+            .collect()
+    }
+
+    /// The location closest to `point`, with its distance in kilometers
+    pub fn nearest_location(&self, point: &GeoPoint) -> Option<(&CloLocation, f64)> {
+        nearest(point, self.geo_locations())
+    }
+}
@@ -0,0 +1,271 @@
+//! Per-call credit ledger and cost reports
+
+use crate::{
+    dates::civil_from_days,
+    error::{ErrorKind, Result},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+/// One SDK call as recorded in the ledger
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    /// Unix time of the call, in milliseconds
+    pub timestamp: u64,
+    pub endpoint: String,
+    /// Credits charged for this call. Calls answered from the cache or by sharing an
+    /// identical in-flight request are charged nothing.
+    pub credits: i64,
+    pub confidence_level: Option<i32>,
+    pub cache_hit: bool,
+    /// Whether the call shared the response of an identical in-flight request
+    #[serde(default)]
+    pub shared: bool,
+    /// Tag of the caller, set with [`CufinderSDK::tagged`](crate::CufinderSDK::tagged)
+    pub tag: Option<String>,
+    /// Kind of error, for failed calls
+    pub error: Option<ErrorKind>,
+}
+
+impl LedgerEntry {
+    /// UTC day of the call as `YYYY-MM-DD`
+    pub fn day(&self) -> String {
+        let (year, month, day) = civil_from_days((self.timestamp / 86_400_000) as i64);
+        format!("{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+/// Destination for ledger entries
+pub trait LedgerSink: Send + Sync + fmt::Debug {
+    /// Record one call
+    fn record(&self, entry: &LedgerEntry) -> Result<()>;
+
+    /// Whether `record` does blocking I/O, such as file or network access. The client
+    /// then calls it on a blocking thread instead of the async executor.
+    fn is_blocking(&self) -> bool {
+        false
+    }
+}
+
+impl<T: LedgerSink + ?Sized> LedgerSink for Arc<T> {
+    fn record(&self, entry: &LedgerEntry) -> Result<()> {
+        (**self).record(entry)
+    }
+
+    fn is_blocking(&self) -> bool {
+        (**self).is_blocking()
+    }
+}
+
+/// Ledger kept in memory, for tests and short-lived reports
+#[derive(Debug, Default)]
+pub struct MemoryLedger {
+    entries: Mutex<Vec<LedgerEntry>>,
+}
+
+impl MemoryLedger {
+    /// An empty ledger
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Entries recorded so far, oldest first
+    pub fn entries(&self) -> Vec<LedgerEntry> {
+        self.lock().clone()
+    }
+
+    /// Aggregate the entries recorded so far
+    pub fn report(&self) -> LedgerReport {
+        LedgerReport::from_entries(self.lock().iter())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<LedgerEntry>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl LedgerSink for MemoryLedger {
+    fn record(&self, entry: &LedgerEntry) -> Result<()> {
+        self.lock().push(entry.clone());
+        Ok(())
+    }
+}
+
+/// Ledger appended to a local JSONL file, one entry per line.
+///
+/// Writes block, so the client records entries on a blocking thread.
+#[derive(Debug)]
+pub struct FileLedger {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl FileLedger {
+    /// Open `path` for appending, creating it if needed
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Path of the ledger file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read every entry from a ledger file, skipping a truncated last line
+    pub fn read(path: impl AsRef<Path>) -> Result<Vec<LedgerEntry>> {
+        let mut entries = Vec::new();
+        for line in BufReader::new(File::open(path)?).lines() {
+            let line = line?;
+            if let Ok(entry) = serde_json::from_str(&line) {
+                entries.push(entry);
+            }
+        }
+        Ok(entries)
+    }
+}
+
+impl LedgerSink for FileLedger {
+    fn record(&self, entry: &LedgerEntry) -> Result<()> {
+        let line = serde_json::to_string(entry)?;
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        writeln!(file, "{}", line)?;
+        file.flush()?;
+        Ok(())
+    }
+
+    fn is_blocking(&self) -> bool {
+        true
+    }
+}
+
+/// Calls and credits in one group of a [`LedgerReport`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LedgerTotals {
+    pub calls: u64,
+    pub credits: i64,
+    pub cache_hits: u64,
+    pub errors: u64,
+    /// Successful calls sent by this caller, neither cached nor shared
+    pub charged: u64,
+    /// Credits of the `charged` calls
+    pub charged_credits: i64,
+}
+
+impl LedgerTotals {
+    fn add(&mut self, entry: &LedgerEntry) {
+        self.calls += 1;
+        self.credits += entry.credits;
+        self.cache_hits += u64::from(entry.cache_hit);
+        self.errors += u64::from(entry.error.is_some());
+        if !entry.cache_hit && !entry.shared && entry.error.is_none() {
+            self.charged += 1;
+            self.charged_credits += entry.credits;
+        }
+    }
+}
+
+/// Key of one CSV row: UTC day, caller tag and endpoint
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LedgerRowKey {
+    pub day: String,
+    pub tag: Option<String>,
+    pub endpoint: String,
+}
+
+/// Ledger entries aggregated by endpoint, tag and day
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LedgerReport {
+    pub total: LedgerTotals,
+    pub by_endpoint: BTreeMap<String, LedgerTotals>,
+    /// Totals by caller tag; untagged calls are grouped under `""`
+    pub by_tag: BTreeMap<String, LedgerTotals>,
+    /// Totals by UTC day, `YYYY-MM-DD`
+    pub by_day: BTreeMap<String, LedgerTotals>,
+    /// Totals by day, tag and endpoint together
+    pub rows: BTreeMap<LedgerRowKey, LedgerTotals>,
+}
+
+impl LedgerReport {
+    /// Aggregate `entries`
+    pub fn from_entries<'a>(entries: impl IntoIterator<Item = &'a LedgerEntry>) -> Self {
+        let mut report = Self::default();
+        for entry in entries {
+            let day = entry.day();
+            report.total.add(entry);
+            report
+                .by_endpoint
+                .entry(entry.endpoint.clone())
+                .or_default()
+                .add(entry);
+            report
+                .by_tag
+                .entry(entry.tag.clone().unwrap_or_default())
+                .or_default()
+                .add(entry);
+            report.by_day.entry(day.clone()).or_default().add(entry);
+            report
+                .rows
+                .entry(LedgerRowKey {
+                    day,
+                    tag: entry.tag.clone(),
+                    endpoint: entry.endpoint.clone(),
+                })
+                .or_default()
+                .add(entry);
+        }
+        report
+    }
+
+    /// Aggregate the entries of a [`FileLedger`] file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self::from_entries(&FileLedger::read(path)?))
+    }
+
+    /// Write one CSV row per day, tag and endpoint, with a header row
+    pub fn write_csv(&self, mut writer: impl Write) -> Result<()> {
+        writeln!(writer, "day,tag,endpoint,calls,credits,cache_hits,errors")?;
+        for (key, totals) in &self.rows {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{}",
+                key.day,
+                csv_field(key.tag.as_deref().unwrap_or("")),
+                csv_field(&key.endpoint),
+                totals.calls,
+                totals.credits,
+                totals.cache_hits,
+                totals.errors
+            )?;
+        }
+        Ok(())
+    }
+
+    /// The report as CSV text, see [`write_csv`](Self::write_csv)
+    pub fn to_csv(&self) -> String {
+        let mut csv = Vec::new();
+        // Writing to a Vec cannot fail
+        let _ = self.write_csv(&mut csv);
+        String::from_utf8(csv).unwrap_or_default()
+    }
+}
+
+/// Quote a CSV field if it contains a delimiter, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -1,11 +1,44 @@
+pub mod api;
+#[cfg(feature = "audit")]
+pub mod audit;
+pub mod batch;
+pub mod builders;
+pub mod bulk;
+pub mod cache;
+pub mod cache_store;
 pub mod client;
+pub mod cost;
+pub mod credits;
+mod de;
+pub mod endpoint;
+pub mod envelope;
 pub mod error;
+pub mod events;
+pub mod geo;
+pub mod ledger;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod pagination;
+pub mod rate_limit;
 pub mod types;
 pub mod services;
+mod single_flight;
+mod trace;
 pub mod sdk;
+pub mod dates;
 
-pub use client::{Client, ClientConfig};
-pub use error::{CufinderError, Result};
+pub use api::CufinderApi;
+pub use builders::{CjaParamsBuilder, CseParamsBuilder, LbsParamsBuilder, PseParamsBuilder};
+pub use cache::{CacheConfig, CacheStats};
+pub use client::{Client, ClientConfig, PreparedRequest, RawResponse};
+pub use credits::{CreditAccountant, CreditBudget, CreditUsage};
+pub use dates::PartialDate;
+pub use endpoint::Endpoint;
+pub use envelope::ApiEnvelope;
+pub use error::{CufinderError, ErrorKind, Result};
+pub use events::{Event, Observer};
+pub use geo::{BoundingBox, GeoPoint};
+pub use pagination::{Page, PagedParams, PagedResponse, PaginationOptions};
 pub use sdk::CufinderSDK;
 pub use types::*;
 
@@ -0,0 +1,318 @@
+//! Usage metrics, with a recorder that renders the Prometheus text format
+//!
+//! ```
+//! use cufinder_rust::{CufinderSDK, metrics::PrometheusMetrics};
+//! use std::sync::Arc;
+//!
+//! let metrics = Arc::new(PrometheusMetrics::new());
+//! let sdk = CufinderSDK::new("your-api-key".to_string())
+//!     .unwrap()
+//!     .with_metrics(metrics.clone());
+//!
+//! // Serve this from your `/metrics` endpoint
+//! let text = metrics.render();
+//! # drop((sdk, text));
+//! ```
+
+use crate::events::Event;
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+/// HTTP requests sent, by `endpoint`. Retries count as separate requests.
+pub const REQUESTS_TOTAL: &str = "cufinder_requests_total";
+/// Failed HTTP requests and calls that failed without one, such as calls over budget or
+/// whose response did not decode, by `endpoint` and error `kind`
+pub const ERRORS_TOTAL: &str = "cufinder_errors_total";
+/// HTTP request latency in seconds, by `endpoint`
+pub const REQUEST_DURATION_SECONDS: &str = "cufinder_request_duration_seconds";
+/// Retries scheduled, by `endpoint` and error `kind`
+pub const RETRIES_TOTAL: &str = "cufinder_retries_total";
+/// Responses served from the cache, by `endpoint`
+pub const CACHE_HITS_TOTAL: &str = "cufinder_cache_hits_total";
+/// Calls answered by an identical in-flight request, by `endpoint`
+pub const COALESCED_TOTAL: &str = "cufinder_coalesced_total";
+/// Credits reported by responses, by `endpoint`
+pub const CREDITS_TOTAL: &str = "cufinder_credits_total";
+/// Time spent waiting for the client-side rate limiter, in seconds, by `endpoint`
+pub const RATE_LIMIT_WAIT_SECONDS: &str = "cufinder_rate_limit_wait_seconds_total";
+
+/// Default latency buckets, in seconds
+pub const DEFAULT_BUCKETS: [f64; 10] = [0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0];
+
+/// Destination for SDK metrics, such as [`PrometheusMetrics`] or an adapter to another
+/// metrics library
+pub trait MetricsRecorder: Send + Sync {
+    /// Add `value` to the counter `name` with `labels`
+    fn increment(&self, name: &'static str, labels: &[(&'static str, &str)], value: f64);
+
+    /// Record one observation of `value` in the histogram `name` with `labels`
+    fn observe(&self, name: &'static str, labels: &[(&'static str, &str)], value: f64);
+}
+
+impl<T: MetricsRecorder + ?Sized> MetricsRecorder for Arc<T> {
+    fn increment(&self, name: &'static str, labels: &[(&'static str, &str)], value: f64) {
+        (**self).increment(name, labels, value)
+    }
+
+    fn observe(&self, name: &'static str, labels: &[(&'static str, &str)], value: f64) {
+        (**self).observe(name, labels, value)
+    }
+}
+
+/// Translate an SDK [`Event`] into metrics
+pub(crate) fn record_event(recorder: &dyn MetricsRecorder, event: &Event) {
+    match event {
+        Event::RequestFinished {
+            endpoint,
+            latency,
+            error,
+            ..
+        } => {
+            let endpoint = [("endpoint", endpoint.as_str())];
+            recorder.increment(REQUESTS_TOTAL, &endpoint, 1.0);
+            recorder.observe(REQUEST_DURATION_SECONDS, &endpoint, latency.as_secs_f64());
+            if let Some(error) = error {
+                let labels = [endpoint[0], ("kind", error.as_str())];
+                recorder.increment(ERRORS_TOTAL, &labels, 1.0);
+            }
+        }
+        Event::CallFailed { endpoint, error } => {
+            let labels = [("endpoint", endpoint.as_str()), ("kind", error.as_str())];
+            recorder.increment(ERRORS_TOTAL, &labels, 1.0);
+        }
+        Event::RetryScheduled {
+            endpoint, error, ..
+        } => {
+            let labels = [("endpoint", endpoint.as_str()), ("kind", error.as_str())];
+            recorder.increment(RETRIES_TOTAL, &labels, 1.0);
+        }
+        Event::RateLimitWait { endpoint, wait } => {
+            let labels = [("endpoint", endpoint.as_str())];
+            recorder.increment(RATE_LIMIT_WAIT_SECONDS, &labels, wait.as_secs_f64());
+        }
+        Event::CreditsConsumed { endpoint, credits } => {
+            let labels = [("endpoint", endpoint.as_str())];
+            recorder.increment(CREDITS_TOTAL, &labels, f64::from(*credits));
+        }
+        Event::CacheHit { endpoint } => {
+            recorder.increment(CACHE_HITS_TOTAL, &[("endpoint", endpoint.as_str())], 1.0);
+        }
+        Event::RequestCoalesced { endpoint } => {
+            recorder.increment(COALESCED_TOTAL, &[("endpoint", endpoint.as_str())], 1.0);
+        }
+        _ => {}
+    }
+}
+
+/// Description of a known metric, for `# HELP` lines
+fn help(name: &str) -> Option<&'static str> {
+    Some(match name {
+        REQUESTS_TOTAL => "HTTP requests sent to the CUFinder API, including retries",
+        ERRORS_TOTAL => "Failed CUFinder API requests and calls, by error kind",
+        REQUEST_DURATION_SECONDS => "Latency of HTTP requests to the CUFinder API",
+        RETRIES_TOTAL => "Retries of failed CUFinder API requests, by error kind",
+        CACHE_HITS_TOTAL => "CUFinder responses served from the cache",
+        COALESCED_TOTAL => "CUFinder calls answered by an identical in-flight request",
+        CREDITS_TOTAL => "CUFinder credits reported by responses",
+        RATE_LIMIT_WAIT_SECONDS => "Time spent waiting for the client-side rate limiter",
+        _ => return None,
+    })
+}
+
+type Labels = Vec<(&'static str, String)>;
+
+#[derive(Debug, Clone, Default)]
+struct Histogram {
+    /// Observations per bucket, not cumulative
+    buckets: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+#[derive(Debug, Default)]
+struct Registry {
+    counters: BTreeMap<&'static str, BTreeMap<Labels, f64>>,
+    histograms: BTreeMap<&'static str, BTreeMap<Labels, Histogram>>,
+}
+
+/// In-memory counters and histograms rendered in the Prometheus text exposition format
+#[derive(Debug)]
+pub struct PrometheusMetrics {
+    buckets: Vec<f64>,
+    registry: Mutex<Registry>,
+}
+
+impl Default for PrometheusMetrics {
+    fn default() -> Self {
+        Self::with_buckets(DEFAULT_BUCKETS.to_vec())
+    }
+}
+
+impl PrometheusMetrics {
+    /// Empty metrics with the [`DEFAULT_BUCKETS`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Empty metrics with histogram `buckets`, upper bounds in seconds
+    pub fn with_buckets(mut buckets: Vec<f64>) -> Self {
+        buckets.retain(|bound| bound.is_finite());
+        buckets.sort_by(f64::total_cmp);
+        buckets.dedup();
+        Self {
+            buckets,
+            registry: Mutex::new(Registry::default()),
+        }
+    }
+
+    /// Current value of the counter `name` with exactly `labels`, if it was ever incremented
+    pub fn counter(&self, name: &str, labels: &[(&str, &str)]) -> Option<f64> {
+        let registry = self.lock();
+        let family = registry.counters.get(name)?;
+        family
+            .iter()
+            .find(|(key, _)| same_labels(key, labels))
+            .map(|(_, value)| *value)
+    }
+
+    /// Forget every recorded value
+    pub fn reset(&self) {
+        *self.lock() = Registry::default();
+    }
+
+    /// All metrics in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        // Writing to a String cannot fail
+        let _ = self.write(&mut out);
+        out
+    }
+
+    fn write(&self, out: &mut String) -> fmt::Result {
+        let registry = self.lock();
+        for (name, family) in &registry.counters {
+            header(out, name, "counter")?;
+            for (labels, value) in family {
+                writeln!(out, "{}{} {}", name, format_labels(labels, None), value)?;
+            }
+        }
+        for (name, family) in &registry.histograms {
+            header(out, name, "histogram")?;
+            for (labels, histogram) in family {
+                let mut cumulative = 0;
+                for (bound, count) in self.buckets.iter().zip(&histogram.buckets) {
+                    cumulative += count;
+                    let le = bound.to_string();
+                    writeln!(
+                        out,
+                        "{}_bucket{} {}",
+                        name,
+                        format_labels(labels, Some(&le)),
+                        cumulative
+                    )?;
+                }
+                let all = format_labels(labels, Some("+Inf"));
+                writeln!(out, "{}_bucket{} {}", name, all, histogram.count)?;
+                writeln!(
+                    out,
+                    "{}_sum{} {}",
+                    name,
+                    format_labels(labels, None),
+                    histogram.sum
+                )?;
+                writeln!(
+                    out,
+                    "{}_count{} {}",
+                    name,
+                    format_labels(labels, None),
+                    histogram.count
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Registry> {
+        self.registry.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl MetricsRecorder for PrometheusMetrics {
+    fn increment(&self, name: &'static str, labels: &[(&'static str, &str)], value: f64) {
+        let mut registry = self.lock();
+        *registry
+            .counters
+            .entry(name)
+            .or_default()
+            .entry(owned_labels(labels))
+            .or_default() += value;
+    }
+
+    fn observe(&self, name: &'static str, labels: &[(&'static str, &str)], value: f64) {
+        let mut registry = self.lock();
+        let histogram = registry
+            .histograms
+            .entry(name)
+            .or_default()
+            .entry(owned_labels(labels))
+            .or_insert_with(|| Histogram {
+                buckets: vec![0; self.buckets.len()],
+                ..Histogram::default()
+            });
+        if let Some(bucket) = self.buckets.iter().position(|bound| value <= *bound) {
+            histogram.buckets[bucket] += 1;
+        }
+        histogram.sum += value;
+        histogram.count += 1;
+    }
+}
+
+fn owned_labels(labels: &[(&'static str, &str)]) -> Labels {
+    let mut labels: Labels = labels
+        .iter()
+        .map(|(name, value)| (*name, value.to_string()))
+        .collect();
+    labels.sort();
+    labels
+}
+
+fn same_labels(key: &Labels, labels: &[(&str, &str)]) -> bool {
+    key.len() == labels.len()
+        && labels
+            .iter()
+            .all(|(name, value)| key.iter().any(|(n, v)| n == name && v == value))
+}
+
+fn header(out: &mut String, name: &str, kind: &str) -> fmt::Result {
+    if let Some(help) = help(name) {
+        writeln!(out, "# HELP {} {}", name, help)?;
+    }
+    writeln!(out, "# TYPE {} {}", name, kind)
+}
+
+/// `{name="value",...}`, with an optional `le` bucket label, or nothing without labels
+fn format_labels(labels: &Labels, le: Option<&str>) -> String {
+    let mut pairs: Vec<String> = labels
+        .iter()
+        .map(|(name, value)| format!("{}=\"{}\"", name, escape(value)))
+        .collect();
+    if let Some(le) = le {
+        pairs.push(format!("le=\"{}\"", le));
+    }
+    if pairs.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", pairs.join(","))
+    }
+}
+
+/// Escape a label value as the text format requires
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
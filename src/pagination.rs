@@ -0,0 +1,349 @@
+//! Auto-pagination over paged search endpoints
+
+use crate::{
+    error::Result,
+    events::{Event, EventSink},
+    types::*,
+};
+use futures::{Stream, StreamExt, TryStreamExt, stream};
+use serde_json::Value;
+use std::future::Future;
+
+/// Limits and starting point for auto-paginating streams
+#[derive(Debug, Clone, Default)]
+pub struct PaginationOptions {
+    /// Page to start from, overriding the page set on the params. Defaults to 1.
+    pub start_page: Option<i32>,
+    /// Stop after fetching this many pages
+    pub max_pages: Option<usize>,
+    /// Stop after yielding this many items
+    pub max_items: Option<usize>,
+}
+
+impl PaginationOptions {
+    /// Options with no limits, starting from the first page
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resume from `page`
+    pub fn start_page(mut self, page: i32) -> Self {
+        self.start_page = Some(page);
+        self
+    }
+
+    /// Fetch at most `max_pages` pages
+    pub fn max_pages(mut self, max_pages: usize) -> Self {
+        self.max_pages = Some(max_pages);
+        self
+    }
+
+    /// Yield at most `max_items` items
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+}
+
+/// Request parameters that select a page
+pub trait PagedParams: Clone {
+    /// The requested page, if set
+    fn page(&self) -> Option<i32>;
+
+    /// Select a page
+    fn set_page(&mut self, page: i32);
+}
+
+/// A response holding one page of items
+pub trait PagedResponse {
+    /// The item type listed on each page
+    type Item;
+
+    /// Items on this page
+    fn items(&self) -> &[Self::Item];
+
+    /// Consume the response, returning its items
+    fn into_items(self) -> Vec<Self::Item>;
+
+    /// Response metadata, which may carry pagination markers
+    fn meta_data(&self) -> Option<&Value>;
+
+    /// Convert into a [`Page`], taking the page number from `params` when the
+    /// metadata does not report it
+    fn into_page<P: PagedParams>(self, params: &P) -> Page<Self::Item>
+    where
+        Self: Sized,
+    {
+        let meta_data = self.meta_data().cloned();
+        Page::new(self.into_items(), meta_data, params.page().unwrap_or(1))
+    }
+}
+
+macro_rules! impl_paged {
+    ($params:ty => $response:ty, $field:ident: $item:ty) => {
+        impl PagedParams for $params {
+            fn page(&self) -> Option<i32> {
+                self.page
+            }
+
+            fn set_page(&mut self, page: i32) {
+                self.page = Some(page);
+            }
+        }
+
+        impl PagedResponse for $response {
+            type Item = $item;
+
+            fn items(&self) -> &[$item] {
+                &self.$field
+            }
+
+            fn into_items(self) -> Vec<$item> {
+                self.$field
+            }
+
+            fn meta_data(&self) -> Option<&Value> {
+                self.base.meta_data.as_ref()
+            }
+        }
+    };
+}
+
+impl_paged!(CseParams => CseResponse, companies: Company);
+impl_paged!(PseParams => PseResponse, peoples: Person);
+impl_paged!(LbsParams => LbsResponse, companies: Company);
+impl_paged!(CjaParams => CjaResponse, jobs: CjaJobItem);
+impl_paged!(CefParams => CefResponse, employees: CefEmployee);
+impl_paged!(CaaParams => CaaResponse, activities: CaaActivity);
+
+/// One page of results with its pagination metadata
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    /// Items on this page
+    pub items: Vec<T>,
+    /// Page number, starting at 1
+    pub page: i32,
+    /// Items per page, from metadata or [`with_page_size`](Self::with_page_size), or else
+    /// the number of items on this page
+    pub page_size: usize,
+    /// Total number of results, if reported
+    pub total: Option<u64>,
+    /// Total number of pages, if reported
+    pub total_pages: Option<i32>,
+    /// Raw response metadata
+    pub meta_data: Option<Value>,
+    /// Whether `page_size` came from metadata, the first page or
+    /// [`with_page_size`](Self::with_page_size), rather than from a possibly short page
+    size_known: bool,
+}
+
+fn meta_i64(meta: Option<&Value>, keys: &[&str]) -> Option<i64> {
+    let meta = meta?;
+    keys.iter().find_map(|key| match meta.get(key)? {
+        Value::Number(n) => n.as_i64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    })
+}
+
+impl<T> Page<T> {
+    /// Build a page from its items and metadata. `requested_page` is used when the
+    /// metadata does not report the current page.
+    pub fn new(items: Vec<T>, meta_data: Option<Value>, requested_page: i32) -> Self {
+        let meta = meta_data.as_ref();
+        let page = meta_i64(meta, &["page", "current_page"])
+            .and_then(|p| i32::try_from(p).ok())
+            .unwrap_or(requested_page);
+        let reported_size = meta_i64(meta, &["page_size", "per_page", "limit"])
+            .and_then(|n| usize::try_from(n).ok())
+            .filter(|n| *n > 0);
+        let size_known = reported_size.is_some() || page <= 1;
+        let page_size = reported_size.unwrap_or(items.len());
+        let total = meta_i64(meta, &["total", "total_results", "total_count"])
+            .and_then(|n| u64::try_from(n).ok());
+        let total_pages = meta_i64(meta, &["total_pages", "last_page", "page_count"])
+            .and_then(|n| i32::try_from(n).ok());
+
+        Self {
+            items,
+            page,
+            page_size,
+            total,
+            total_pages,
+            meta_data,
+            size_known,
+        }
+    }
+
+    /// Use `page_size`, such as the length of the first page, when the metadata does not
+    /// report one
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        if !self.size_known && page_size > 0 {
+            self.page_size = page_size;
+            self.size_known = true;
+        }
+        self
+    }
+
+    /// Whether another page is likely to exist.
+    ///
+    /// Uses explicit `has_more`/`has_next` flags, then page or result totals, then
+    /// `next_page`, and finally assumes more results while pages are full. A result total
+    /// is only compared when the page size is known: taking it from a short last page
+    /// would understate how many results the earlier pages held.
+    pub fn has_next(&self) -> bool {
+        let meta = self.meta_data.as_ref();
+        if let Some(more) = meta.and_then(|m| {
+            ["has_more", "has_next", "has_next_page"]
+                .iter()
+                .find_map(|key| m.get(key).and_then(Value::as_bool))
+        }) {
+            return more;
+        }
+        if let Some(total_pages) = self.total_pages {
+            return self.page < total_pages;
+        }
+        if let Some(total) = self.total {
+            let seen = (self.page.max(0) as u64) * (self.page_size as u64);
+            // With an unknown page size, `seen` is only a lower bound on the results so far
+            if self.size_known || seen >= total {
+                return seen < total;
+            }
+        }
+        if let Some(next) = meta.and_then(|m| m.get("next_page")) {
+            return !next.is_null();
+        }
+        !self.items.is_empty() && self.items.len() >= self.page_size
+    }
+
+    /// The next page number, if [`has_next`](Self::has_next)
+    pub fn next_page(&self) -> Option<i32> {
+        self.has_next().then_some(self.page + 1)
+    }
+
+    /// A copy of `params` selecting the next page, if [`has_next`](Self::has_next)
+    pub fn next_params<P: PagedParams>(&self, params: &P) -> Option<P> {
+        let page = self.next_page()?;
+        let mut params = params.clone();
+        params.set_page(page);
+        Some(params)
+    }
+
+    /// Number of items on this page
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Whether this page has no items
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl<T> IntoIterator for Page<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+struct PageState<P> {
+    params: P,
+    page: i32,
+    pages_fetched: usize,
+    page_size: Option<usize>,
+    done: bool,
+}
+
+/// Lazily walk pages by calling `fetch` with `params` for each page, yielding individual items.
+///
+/// Stops on an empty page, a page shorter than the first one, a metadata end marker,
+/// the first error, or the limits in `options`.
+pub fn paginate<P, R, F, Fut>(
+    params: P,
+    options: PaginationOptions,
+    fetch: F,
+) -> impl Stream<Item = Result<R::Item>>
+where
+    P: PagedParams,
+    R: PagedResponse,
+    F: Fn(P) -> Fut,
+    Fut: Future<Output = Result<R>>,
+{
+    paginate_observed(params, options, EventSink::default(), fetch)
+}
+
+/// [`paginate`], reporting each fetched page to `events`
+pub(crate) fn paginate_observed<P, R, F, Fut>(
+    params: P,
+    options: PaginationOptions,
+    events: EventSink,
+    fetch: F,
+) -> impl Stream<Item = Result<R::Item>>
+where
+    P: PagedParams,
+    R: PagedResponse,
+    F: Fn(P) -> Fut,
+    Fut: Future<Output = Result<R>>,
+{
+    let page = options.start_page.or(params.page()).unwrap_or(1);
+    let state = PageState {
+        params,
+        page,
+        pages_fetched: 0,
+        page_size: None,
+        done: false,
+    };
+    let max_pages = options.max_pages;
+
+    let pages = stream::try_unfold((state, fetch), move |(state, fetch)| {
+        next_page(state, fetch, max_pages, events.clone())
+    });
+
+    pages
+        .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+        .try_flatten()
+        .take(options.max_items.unwrap_or(usize::MAX))
+}
+
+async fn next_page<P, R, F, Fut>(
+    mut state: PageState<P>,
+    fetch: F,
+    max_pages: Option<usize>,
+    events: EventSink,
+) -> Result<Option<(Vec<R::Item>, (PageState<P>, F))>>
+where
+    P: PagedParams,
+    R: PagedResponse,
+    F: Fn(P) -> Fut,
+    Fut: Future<Output = Result<R>>,
+{
+    if state.done || max_pages.is_some_and(|max| state.pages_fetched >= max) {
+        return Ok(None);
+    }
+
+    let mut params = state.params.clone();
+    params.set_page(state.page);
+    let response = fetch(params.clone()).await?;
+
+    let mut page = response.into_page(&params);
+    if let Some(page_size) = state.page_size {
+        page = page.with_page_size(page_size);
+    }
+    events.emit(|| Event::PageFetched {
+        page: page.page,
+        items: page.len(),
+        total: page.total,
+    });
+    if page.is_empty() {
+        return Ok(None);
+    }
+    let page_size = *state.page_size.get_or_insert(page.len());
+    state.done = page.len() < page_size || !page.has_next();
+    state.pages_fetched += 1;
+    state.page += 1;
+
+    Ok(Some((page.items, (state, fetch))))
+}
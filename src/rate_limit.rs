@@ -0,0 +1,72 @@
+//! Client-side request rate limiting
+
+use crate::error::{CufinderError, Result};
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Spaces requests evenly so that no more than a fixed number are sent per second.
+///
+/// Shared by every clone of a [`Client`](crate::Client), so concurrent tasks draw from the same budget.
+#[derive(Debug)]
+pub struct RateLimiter {
+    interval: Duration,
+    next_slot: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    /// Allow up to `requests_per_second` requests per second.
+    ///
+    /// # Panics
+    ///
+    /// If the rate is invalid, see [`try_new`](Self::try_new).
+    pub fn new(requests_per_second: f64) -> Self {
+        Self::try_new(requests_per_second).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Allow up to `requests_per_second` requests per second. An infinite rate sends
+    /// requests without spacing; zero, negative and NaN rates, and rates so low that the
+    /// spacing cannot be represented, fail with [`CufinderError::ValidationError`].
+    pub fn try_new(requests_per_second: f64) -> Result<Self> {
+        if requests_per_second.is_nan() || requests_per_second <= 0.0 {
+            return Err(CufinderError::ValidationError(format!(
+                "rate limit must be a positive number of requests per second, got {}",
+                requests_per_second
+            )));
+        }
+        let interval = Duration::try_from_secs_f64(1.0 / requests_per_second).map_err(|_| {
+            CufinderError::ValidationError(format!(
+                "rate limit of {} requests per second is too low",
+                requests_per_second
+            ))
+        })?;
+        Ok(Self {
+            interval,
+            next_slot: Mutex::new(Instant::now()),
+        })
+    }
+
+    /// Minimum spacing between requests
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Reserve the next request slot, returning how long the caller must wait for it
+    pub fn reserve(&self) -> Duration {
+        let mut next_slot = self.next_slot.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        let slot = (*next_slot).max(now);
+        *next_slot = slot + self.interval;
+        slot - now
+    }
+
+    /// Wait until the next request may be sent, returning the time spent waiting
+    pub async fn acquire(&self) -> Duration {
+        let wait = self.reserve();
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+        wait
+    }
+}
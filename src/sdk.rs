@@ -1,9 +1,21 @@
 use crate::{
-    client::{Client, ClientConfig},
+    batch::{self, BatchOptions, BatchOutput, EnrichOptions},
+    cache::CacheConfig,
+    cache_store::CacheStore,
+    credits::{CreditAccountant, CreditBudget},
+    endpoint::Endpoint,
+    envelope::ApiEnvelope,
+    ledger::LedgerSink,
+    client::{Client, ClientConfig, RawResponse},
     error::Result,
+    events::Observer,
+    pagination::{PaginationOptions, paginate_observed},
     services::Service,
     types::*,
 };
+use futures::Stream;
+use serde::{Serialize, de::DeserializeOwned};
+use std::future::Future;
 use std::time::Duration;
 
 /// Main CUFinder SDK
@@ -33,11 +45,163 @@ impl CufinderSDK {
         Ok(Self { client, service })
     }
 
+    /// Limit outgoing requests to `requests_per_second`, shared by every call made through this SDK.
+    ///
+    /// # Panics
+    ///
+    /// If the rate is invalid, see [`RateLimiter::try_new`](crate::rate_limit::RateLimiter::try_new).
+    pub fn with_rate_limit(mut self, requests_per_second: f64) -> Self {
+        self.client = self.client.with_rate_limit(requests_per_second);
+        self.service = Service::new(self.client.clone());
+        self
+    }
+
+    /// Like [`with_rate_limit`](Self::with_rate_limit), but fails with
+    /// [`CufinderError::ValidationError`](crate::CufinderError::ValidationError) instead of
+    /// panicking on an invalid rate
+    pub fn try_with_rate_limit(mut self, requests_per_second: f64) -> Result<Self> {
+        self.client = self.client.try_with_rate_limit(requests_per_second)?;
+        self.service = Service::new(self.client.clone());
+        Ok(self)
+    }
+
+    /// Retry requests that timed out. Off by default, because the API may have charged a
+    /// request that timed out, and retrying it would charge the lookup twice.
+    pub fn with_retry_timeouts(mut self, enabled: bool) -> Self {
+        self.client = self.client.with_retry_timeouts(enabled);
+        self.service = Service::new(self.client.clone());
+        self
+    }
+
+    /// Cache responses in memory so repeated lookups do not spend credits.
+    /// Paginated searches are not cached unless [`CacheConfig::cache_paginated`] is set.
+    pub fn with_cache(mut self, config: CacheConfig) -> Self {
+        self.client = self.client.with_cache(config);
+        self.service = Service::new(self.client.clone());
+        self
+    }
+
+    /// Cache responses in `store`, such as a persistent [`FileStore`](crate::cache_store::FileStore)
+    /// that survives restarts
+    pub fn with_cache_store(mut self, config: CacheConfig, store: impl CacheStore + 'static) -> Self {
+        self.client = self.client.with_cache_store(config, store);
+        self.service = Service::new(self.client.clone());
+        self
+    }
+
+    /// Coalesce concurrent identical requests into one HTTP call. Enabled by default.
+    pub fn with_coalescing(mut self, enabled: bool) -> Self {
+        self.client = self.client.with_coalescing(enabled);
+        self.service = Service::new(self.client.clone());
+        self
+    }
+
+    /// Enforce a credit `budget`: the soft limit runs a callback, the hard limit makes
+    /// further calls fail with [`CufinderError::BudgetExceeded`](crate::CufinderError::BudgetExceeded)
+    /// before sending
+    pub fn with_budget(mut self, budget: CreditBudget) -> Self {
+        self.client = self.client.with_budget(budget);
+        self.service = Service::new(self.client.clone());
+        self
+    }
+
+    /// An SDK sharing this one's connection, cache and limits whose credits are tracked in
+    /// a new scope under `budget`, such as a single batch job
+    pub fn scoped(&self, budget: CreditBudget) -> Self {
+        let client = self.client.scoped(budget);
+        let service = Service::new(client.clone());
+        Self { client, service }
+    }
+
+    /// Credits spent through this SDK, by endpoint
+    pub fn credits(&self) -> &CreditAccountant {
+        self.client.credits()
+    }
+
+    /// In dry-run mode no request is sent: every call fails with
+    /// [`CufinderError::DryRun`](crate::CufinderError::DryRun) holding the encoded request,
+    /// with the API key redacted
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.client = self.client.with_dry_run(dry_run);
+        self.service = Service::new(self.client.clone());
+        self
+    }
+
+    /// Record request parameters, which may contain personal data, in `tracing` spans.
+    /// Off by default; the API key is never recorded.
+    #[cfg(feature = "tracing")]
+    pub fn with_trace_params(mut self, enabled: bool) -> Self {
+        self.client = self.client.with_trace_params(enabled);
+        self.service = Service::new(self.client.clone());
+        self
+    }
+
+    /// Record every call, with its credits, cache use and caller tag, in `ledger`
+    pub fn with_ledger(mut self, ledger: impl LedgerSink + 'static) -> Self {
+        self.client = self.client.with_ledger(ledger);
+        self.service = Service::new(self.client.clone());
+        self
+    }
+
+    /// Append a hash-chained record of every call to a personal-data endpoint (`rel`, `epp`,
+    /// `fwe`, `tep`, `pse`) to `audit`, with identifiers redacted, see [`crate::audit`]
+    #[cfg(feature = "audit")]
+    pub fn with_audit(mut self, audit: crate::audit::AuditLog) -> Self {
+        self.client = self.client.with_audit(audit);
+        self.service = Service::new(self.client.clone());
+        self
+    }
+
+    /// An SDK sharing everything with this one whose ledger and audit entries carry `tag`,
+    /// such as the team or job making the calls
+    pub fn tagged(&self, tag: impl Into<String>) -> Self {
+        let client = self.client.tagged(tag);
+        let service = Service::new(client.clone());
+        Self { client, service }
+    }
+
+    /// Report progress [`Event`](crate::Event)s to `observer`: requests, retries, rate-limit waits,
+    /// credits, fetched pages and batch items
+    pub fn with_observer(mut self, observer: impl Observer + 'static) -> Self {
+        self.client = self.client.with_observer(observer);
+        self.service = Service::new(self.client.clone());
+        self
+    }
+
+    /// Record per-endpoint requests, errors by kind, latency, retries, cache hits and credits
+    /// in `metrics`, such as a shared [`PrometheusMetrics`](crate::metrics::PrometheusMetrics)
+    #[cfg(feature = "metrics")]
+    pub fn with_metrics(mut self, metrics: impl crate::metrics::MetricsRecorder + 'static) -> Self {
+        self.client = self.client.with_metrics(metrics);
+        self.service = Service::new(self.client.clone());
+        self
+    }
+
     /// Get the underlying HTTP client for advanced usage
     pub fn client(&self) -> &Client {
         &self.client
     }
 
+    /// Call any [`Endpoint`] with its parameters, see [`crate::endpoint`]
+    pub async fn call<E: Endpoint>(&self, params: E::Params) -> Result<E::Response> {
+        self.service.call::<E>(params).await
+    }
+
+    /// Call an endpoint the SDK does not model yet, such as `"/new-endpoint"`, and get the
+    /// response status, headers and untouched body. Bypasses the cache and coalescing, see
+    /// [`Client::post_raw`].
+    pub async fn call_raw(&self, path: &str, params: impl Serialize) -> Result<RawResponse> {
+        self.client.post_raw(path, &params).await
+    }
+
+    /// Call an endpoint the SDK does not model yet and decode its `data` as `T`. Like
+    /// [`call_raw`](Self::call_raw), bypasses the cache and coalescing, see [`Client::post_raw`].
+    pub async fn call_json<T: DeserializeOwned>(&self, path: &str, params: impl Serialize) -> Result<T> {
+        let raw = self.client.post_raw(path, &params).await?;
+        let envelope = ApiEnvelope::from_body(&raw.body).inspect_err(|error| self.client.call_failed(path, error))?;
+        Ok(envelope.data)
+    }
+
     // Company Services
 
     /// CUF - Get company domain from company name
@@ -265,4 +429,137 @@ impl CufinderSDK {
     pub async fn cja(&self, params: CjaParams) -> Result<CjaResponse> {
         self.service.get_company_jobs(params).await
     }
+
+    // Auto-paginating Streams
+
+    /// CSE - Stream companies across result pages
+    pub fn cse_stream(
+        &self,
+        params: CseParams,
+        options: PaginationOptions,
+    ) -> impl Stream<Item = Result<Company>> + '_ {
+        paginate_observed(params, options, self.client.events().clone(), move |params| self.service.search_companies(params))
+    }
+
+    /// PSE - Stream people across result pages
+    pub fn pse_stream(
+        &self,
+        params: PseParams,
+        options: PaginationOptions,
+    ) -> impl Stream<Item = Result<Person>> + '_ {
+        paginate_observed(params, options, self.client.events().clone(), move |params| self.service.search_people(params))
+    }
+
+    /// LBS - Stream local businesses across result pages
+    pub fn lbs_stream(
+        &self,
+        params: LbsParams,
+        options: PaginationOptions,
+    ) -> impl Stream<Item = Result<Company>> + '_ {
+        paginate_observed(params, options, self.client.events().clone(), move |params| self.service.search_local_businesses(params))
+    }
+
+    /// CJA - Stream jobs across result pages
+    pub fn cja_stream(
+        &self,
+        params: CjaParams,
+        options: PaginationOptions,
+    ) -> impl Stream<Item = Result<CjaJobItem>> + '_ {
+        paginate_observed(params, options, self.client.events().clone(), move |params| self.service.get_company_jobs(params))
+    }
+
+    /// CEF - Stream company employees across result pages
+    pub fn cef_stream(
+        &self,
+        query: &str,
+        options: PaginationOptions,
+    ) -> impl Stream<Item = Result<CefEmployee>> + '_ {
+        let params = CefParams {
+            query: query.to_string(),
+            page: None,
+        };
+        paginate_observed(params, options, self.client.events().clone(), move |params| self.service.find_company_employees(params))
+    }
+
+    /// CAA - Stream company activities across result pages
+    pub fn caa_stream(
+        &self,
+        query: &str,
+        options: PaginationOptions,
+    ) -> impl Stream<Item = Result<CaaActivity>> + '_ {
+        let params = CaaParams {
+            query: query.to_string(),
+            page: None,
+        };
+        paginate_observed(params, options, self.client.events().clone(), move |params| self.service.get_company_activities(params))
+    }
+
+    // Batch Execution
+
+    /// Run `call` for every input with bounded concurrency.
+    ///
+    /// Results are returned in input order, together with a summary of successes,
+    /// failures by kind and credits used. Each call goes through the client's retry
+    /// and rate limiting.
+    ///
+    /// ```no_run
+    /// # async fn run(sdk: cufinder_rust::CufinderSDK) {
+    /// use cufinder_rust::batch::BatchOptions;
+    ///
+    /// let domains = vec!["stripe.com", "openai.com"];
+    /// let output = sdk
+    ///     .batch(domains, BatchOptions::new().concurrency(4), |sdk, domain| async move {
+    ///         sdk.enc(domain).await
+    ///     })
+    ///     .await;
+    /// println!("{} credits used", output.summary.credits_used);
+    /// # }
+    /// ```
+    pub async fn batch<'a, I, F, Fut, R>(&'a self, inputs: I, options: BatchOptions, mut call: F) -> BatchOutput<R>
+    where
+        I: IntoIterator,
+        F: FnMut(&'a Self, I::Item) -> Fut,
+        Fut: Future<Output = Result<R>> + 'a,
+        R: ApiResponse,
+    {
+        batch::run(inputs, &options, self.client.events(), |input| call(self, input)).await
+    }
+
+    /// Enrich an unbounded stream of records, emitting each record with its result.
+    ///
+    /// Runs up to `options.concurrency` calls at once and only pulls more input when a
+    /// slot frees up, so a slow API applies backpressure to the source. Results are
+    /// emitted as soon as they complete unless [`EnrichOptions::ordered`] is set.
+    /// Dropping the returned stream cancels in-flight calls. Calls go through this
+    /// SDK's client and so share its rate limit and retries.
+    ///
+    /// ```no_run
+    /// # async fn run(sdk: cufinder_rust::CufinderSDK) {
+    /// use cufinder_rust::batch::EnrichOptions;
+    /// use futures::{StreamExt, stream};
+    ///
+    /// let domains = stream::iter(vec!["stripe.com", "openai.com"]);
+    /// let mut results = sdk.enrich_stream(domains, EnrichOptions::new().concurrency(4), |sdk, domain| async move {
+    ///     sdk.enc(domain).await
+    /// });
+    /// while let Some((domain, result)) = results.next().await {
+    ///     println!("{domain}: {}", result.is_ok());
+    /// }
+    /// # }
+    /// ```
+    pub fn enrich_stream<'a, S, F, Fut, R>(
+        &'a self,
+        input: S,
+        options: EnrichOptions,
+        mut call: F,
+    ) -> impl Stream<Item = (S::Item, Result<R>)> + 'a
+    where
+        S: Stream + 'a,
+        S::Item: Clone,
+        F: FnMut(&'a Self, S::Item) -> Fut + 'a,
+        Fut: Future<Output = Result<R>> + 'a,
+        R: ApiResponse + 'a,
+    {
+        batch::enrich(input, options, self.client.events().clone(), move |record| call(self, record))
+    }
 }
@@ -1,6 +1,7 @@
 use crate::{
     client::Client,
-    error::{CufinderError, Result},
+    endpoint::*,
+    error::Result,
     types::*,
 };
 
@@ -15,313 +16,170 @@ impl Service {
         Self { client }
     }
 
+    /// Validate `params`, send them to endpoint `E` and decode its response
+    pub async fn call<E: Endpoint>(&self, params: E::Params) -> Result<E::Response> {
+        E::validate(&params).inspect_err(|error| self.client.call_failed(E::PATH, error))?;
+        let envelope = self.client.post_envelope(E::PATH, &params).await?;
+        Ok(E::from_envelope(envelope))
+    }
+
     /// CUF Service - Company URL Finder
     pub async fn get_domain(&self, params: CufParams) -> Result<CufResponse> {
-        if params.company_name.is_empty() {
-            return Err(CufinderError::ValidationError("company_name is required".to_string()));
-        }
-        if params.country_code.is_empty() {
-            return Err(CufinderError::ValidationError("country_code is required".to_string()));
-        }
-
-        let response = self.client.post("/cuf", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Cuf>(params).await
     }
 
     /// LCUF Service - LinkedIn Company URL Finder
     pub async fn get_linkedin_url(&self, params: LcufParams) -> Result<LcufResponse> {
-        if params.company_name.is_empty() {
-            return Err(CufinderError::ValidationError("company_name is required".to_string()));
-        }
-
-        let response = self.client.post("/lcuf", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Lcuf>(params).await
     }
 
     /// DTC Service - Domain to Company
     pub async fn get_company_name(&self, params: DtcParams) -> Result<DtcResponse> {
-        if params.company_website.is_empty() {
-            return Err(CufinderError::ValidationError("company_website is required".to_string()));
-        }
-
-        let response = self.client.post("/dtc", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Dtc>(params).await
     }
 
     /// DTE Service - Domain to Emails
     pub async fn get_emails(&self, params: DteParams) -> Result<DteResponse> {
-        if params.company_website.is_empty() {
-            return Err(CufinderError::ValidationError("company_website is required".to_string()));
-        }
-
-        let response = self.client.post("/dte", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Dte>(params).await
     }
 
     /// NTP Service - Name to Phones
     pub async fn get_phones(&self, params: NtpParams) -> Result<NtpResponse> {
-        if params.company_name.is_empty() {
-            return Err(CufinderError::ValidationError("company_name is required".to_string()));
-        }
-
-        let response = self.client.post("/ntp", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Ntp>(params).await
     }
 
     /// REL Service - Reverse Email Lookup
     pub async fn reverse_email_lookup(&self, params: RelParams) -> Result<RelResponse> {
-        if params.email.is_empty() {
-            return Err(CufinderError::ValidationError("email is required".to_string()));
-        }
-
-        let response = self.client.post("/rel", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Rel>(params).await
     }
 
     /// FCL Service - Find Company Lookalikes
     pub async fn get_lookalikes(&self, params: FclParams) -> Result<FclResponse> {
-        if params.query.is_empty() {
-            return Err(CufinderError::ValidationError("query is required".to_string()));
-        }
-
-        let response = self.client.post("/fcl", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Fcl>(params).await
     }
 
     /// ELF Service - Enrich LinkedIn Fundraising
     pub async fn get_fundraising(&self, params: ElfParams) -> Result<ElfResponse> {
-        if params.query.is_empty() {
-            return Err(CufinderError::ValidationError("query is required".to_string()));
-        }
-
-        let response = self.client.post("/elf", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Elf>(params).await
     }
 
     /// CAR Service - Company Annual Revenue
     pub async fn get_revenue(&self, params: CarParams) -> Result<CarResponse> {
-        if params.query.is_empty() {
-            return Err(CufinderError::ValidationError("query is required".to_string()));
-        }
-
-        let response = self.client.post("/car", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Car>(params).await
     }
 
     /// FCC Service - Find Company Children
     pub async fn get_subsidiaries(&self, params: FccParams) -> Result<FccResponse> {
-        if params.query.is_empty() {
-            return Err(CufinderError::ValidationError("query is required".to_string()));
-        }
-
-        let response = self.client.post("/fcc", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Fcc>(params).await
     }
 
     /// FTS Service - Find Tech Stack
     pub async fn get_tech_stack(&self, params: FtsParams) -> Result<FtsResponse> {
-        if params.query.is_empty() {
-            return Err(CufinderError::ValidationError("query is required".to_string()));
-        }
-
-        let response = self.client.post("/fts", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Fts>(params).await
     }
 
     /// EPP Service - Enrich Profile
     pub async fn enrich_profile(&self, params: EppParams) -> Result<EppResponse> {
-        if params.linkedin_url.is_empty() {
-            return Err(CufinderError::ValidationError("linkedin_url is required".to_string()));
-        }
-
-        let response = self.client.post("/epp", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Epp>(params).await
     }
 
     /// FWE Service - Find Work Email
     pub async fn get_email_from_profile(&self, params: FweParams) -> Result<FweResponse> {
-        if params.linkedin_url.is_empty() {
-            return Err(CufinderError::ValidationError("linkedin_url is required".to_string()));
-        }
-
-        let response = self.client.post("/fwe", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Fwe>(params).await
     }
 
     /// TEP Service - Person Enrichment
     pub async fn enrich_person(&self, params: TepParams) -> Result<TepResponse> {
-        if params.full_name.is_empty() {
-            return Err(CufinderError::ValidationError("full_name is required".to_string()));
-        }
-        if params.company.is_empty() {
-            return Err(CufinderError::ValidationError("company is required".to_string()));
-        }
-
-        let response = self.client.post("/tep", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Tep>(params).await
     }
 
     /// ENC Service - Company Enrichment
     pub async fn enrich_company(&self, params: EncParams) -> Result<EncResponse> {
-        if params.query.is_empty() {
-            return Err(CufinderError::ValidationError("query is required".to_string()));
-        }
-
-        let response = self.client.post("/enc", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Enc>(params).await
     }
 
     /// CEC Service - Company Employee Countries
     pub async fn get_employee_countries(&self, params: CecParams) -> Result<CecResponse> {
-        if params.query.is_empty() {
-            return Err(CufinderError::ValidationError("query is required".to_string()));
-        }
-
-        let response = self.client.post("/cec", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Cec>(params).await
     }
 
     /// CLO Service - Company Locations
     pub async fn get_locations(&self, params: CloParams) -> Result<CloResponse> {
-        if params.query.is_empty() {
-            return Err(CufinderError::ValidationError("query is required".to_string()));
-        }
-
-        let response = self.client.post("/clo", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Clo>(params).await
     }
 
     /// CSE Service - Company Search
     pub async fn search_companies(&self, params: CseParams) -> Result<CseResponse> {
-        let response = self.client.post("/cse", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Cse>(params).await
     }
 
     /// PSE Service - Person Search
     pub async fn search_people(&self, params: PseParams) -> Result<PseResponse> {
-        let response = self.client.post("/pse", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Pse>(params).await
     }
 
     /// LBS Service - Local Business Search
     pub async fn search_local_businesses(&self, params: LbsParams) -> Result<LbsResponse> {
-        let response = self.client.post("/lbs", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Lbs>(params).await
     }
 
     /// BCD - B2B Customers Finder
     pub async fn extract_b2b_customers(&self, params: BcdParams) -> Result<BcdResponse> {
-        if params.url.is_empty() {
-            return Err(CufinderError::ValidationError("url is required".to_string()));
-        }
-
-        let response = self.client.post("/bcd", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Bcd>(params).await
     }
 
     /// CCP - Company Career Page Finder
     pub async fn find_company_careers_page(&self, params: CcpParams) -> Result<CcpResponse> {
-        if params.url.is_empty() {
-            return Err(CufinderError::ValidationError("url is required".to_string()));
-        }
-
-        let response = self.client.post("/ccp", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Ccp>(params).await
     }
 
     /// ISC - Company Saas Checker
     pub async fn is_saas(&self, params: IscParams) -> Result<IscResponse> {
-        if params.url.is_empty() {
-            return Err(CufinderError::ValidationError("url is required".to_string()));
-        }
-
-        let response = self.client.post("/isc", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Isc>(params).await
     }
 
     /// CBC - Company B2B or B2C Checker
     pub async fn get_company_business_type(&self, params: CbcParams) -> Result<CbcResponse> {
-        if params.url.is_empty() {
-            return Err(CufinderError::ValidationError("url is required".to_string()));
-        }
-
-        let response = self.client.post("/cbc", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Cbc>(params).await
     }
 
     /// CSC - Company Mission Statement
     pub async fn get_company_mission_statement(&self, params: CscParams) -> Result<CscResponse> {
-        if params.url.is_empty() {
-            return Err(CufinderError::ValidationError("url is required".to_string()));
-        }
-
-        let response = self.client.post("/csc", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Csc>(params).await
     }
 
     /// CSN - Company Snapshot
     pub async fn get_company_snapshot(&self, params: CsnParams) -> Result<CsnResponse> {
-        if params.url.is_empty() {
-            return Err(CufinderError::ValidationError("url is required".to_string()));
-        }
-
-        let response = self.client.post("/csn", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Csn>(params).await
     }
 
     /// NAO - Phone Number Normalizer
     pub async fn normalize_phone(&self, params: NaoParams) -> Result<NaoResponse> {
-        if params.phone.is_empty() {
-            return Err(CufinderError::ValidationError("phone is required".to_string()));
-        }
-
-        let response = self.client.post("/nao", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Nao>(params).await
     }
 
     /// NAA - Address Normalizer
     pub async fn normalize_address(&self, params: NaaParams) -> Result<NaaResponse> {
-        if params.address.is_empty() {
-            return Err(CufinderError::ValidationError("address is required".to_string()));
-        }
-
-        let response = self.client.post("/naa", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Naa>(params).await
     }
 
     /// CEF Service - Company Employee Finder
     pub async fn find_company_employees(&self, params: CefParams) -> Result<CefResponse> {
-        if params.query.is_empty() {
-            return Err(CufinderError::ValidationError("query is required".to_string()));
-        }
-
-        let response = self.client.post("/cef", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Cef>(params).await
     }
 
     /// NAC Service - Normalize Company Name
     pub async fn normalize_company_name(&self, params: NacParams) -> Result<NacResponse> {
-        if params.company.is_empty() {
-            return Err(CufinderError::ValidationError("company is required".to_string()));
-        }
-
-        let response = self.client.post("/nac", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Nac>(params).await
     }
 
     /// CAA Service - Company Activity API
     pub async fn get_company_activities(&self, params: CaaParams) -> Result<CaaResponse> {
-        if params.query.is_empty() {
-            return Err(CufinderError::ValidationError("query is required".to_string()));
-        }
-
-        let response = self.client.post("/caa", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Caa>(params).await
     }
 
     /// CJA Service - Company Jobs API
     pub async fn get_company_jobs(&self, params: CjaParams) -> Result<CjaResponse> {
-        let response = self.client.post("/cja", &params).await?;
-        serde_json::from_value(response).map_err(CufinderError::JsonError)
+        self.call::<Cja>(params).await
     }
 }
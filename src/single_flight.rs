@@ -0,0 +1,102 @@
+//! Coalescing of identical concurrent requests
+
+use crate::{envelope::ResponseBody, error::Result};
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+use tokio::sync::watch;
+
+type Outcome = Option<Result<ResponseBody>>;
+
+/// Runs at most one call per key at a time, sharing its result with every caller that
+/// asks for the same key while it is in flight
+#[derive(Debug, Default)]
+pub(crate) struct SingleFlight {
+    inflight: Mutex<HashMap<String, watch::Receiver<Outcome>>>,
+    saved: AtomicU64,
+}
+
+enum Role {
+    Leader(watch::Sender<Outcome>),
+    Follower(watch::Receiver<Outcome>),
+}
+
+/// Removes the leader's key when its call finishes or is cancelled
+struct Leader<'a> {
+    group: &'a SingleFlight,
+    key: &'a str,
+}
+
+impl Drop for Leader<'_> {
+    fn drop(&mut self) {
+        self.group.lock().remove(self.key);
+    }
+}
+
+impl SingleFlight {
+    /// Run `call` for `key`, or wait for the identical call already in flight. Returns the
+    /// result and whether it was shared from another caller.
+    ///
+    /// If the caller running the shared call is cancelled, one of the waiters runs it instead.
+    pub(crate) async fn run<F, Fut>(&self, key: &str, call: F) -> (Result<ResponseBody>, bool)
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<ResponseBody>>,
+    {
+        let mut call = Some(call);
+        loop {
+            // Decide under the lock, but never hold it across an await
+            let role = {
+                let mut inflight = self.lock();
+                match inflight.get(key) {
+                    Some(receiver) => Role::Follower(receiver.clone()),
+                    None => {
+                        let (sender, receiver) = watch::channel(None);
+                        inflight.insert(key.to_string(), receiver);
+                        Role::Leader(sender)
+                    }
+                }
+            };
+
+            match role {
+                Role::Leader(sender) => {
+                    let _leader = Leader { group: self, key };
+                    let call = call.take().expect("single-flight call runs once");
+                    let result = call().await;
+                    let shared = match &result {
+                        Ok(body) => Ok(body.clone()),
+                        Err(error) => Err(error.duplicate()),
+                    };
+                    let _ = sender.send(Some(shared));
+                    return (result, false);
+                }
+                Role::Follower(mut receiver) => {
+                    if let Ok(outcome) = receiver.wait_for(Option::is_some).await {
+                        self.saved.fetch_add(1, Ordering::Relaxed);
+                        let result = match outcome.as_ref() {
+                            Some(Ok(body)) => Ok(body.clone()),
+                            Some(Err(error)) => Err(error.duplicate()),
+                            None => unreachable!("waited for an outcome"),
+                        };
+                        return (result, true);
+                    }
+                    // The leader was cancelled before finishing, so try to take over
+                }
+            }
+        }
+    }
+
+    /// Calls answered by sharing another caller's in-flight result
+    pub(crate) fn saved(&self) -> u64 {
+        self.saved.load(Ordering::Relaxed)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, watch::Receiver<Outcome>>> {
+        self.inflight.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
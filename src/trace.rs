@@ -0,0 +1,171 @@
+//! `tracing` spans and events for API calls, compiled to nothing without the `tracing` feature
+//!
+//! Each call gets a `cufinder_request` span with `endpoint`, `attempt`, `status`,
+//! `latency_ms`, `credit_count`, `cache` and `error` fields. Request parameters may contain
+//! personal data, so they are only recorded, as `params`, when enabled with
+//! [`Client::with_trace_params`](crate::Client::with_trace_params). The API key is never recorded.
+
+#[cfg(feature = "tracing")]
+use crate::events::Event;
+use crate::{envelope::Usage, error::ErrorKind};
+use std::{future::Future, time::Duration};
+
+/// How the cache took part in a call, recorded as the span's `cache` field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CacheOutcome {
+    /// No cache applies to the endpoint
+    Bypass,
+    Hit,
+    Miss,
+    /// Shared with an identical in-flight request
+    Shared,
+}
+
+impl CacheOutcome {
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Bypass => "bypass",
+            Self::Hit => "hit",
+            Self::Miss => "miss",
+            Self::Shared => "shared",
+        }
+    }
+}
+
+/// The span of one SDK call
+#[derive(Debug, Clone)]
+pub(crate) struct RequestSpan {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+}
+
+impl RequestSpan {
+    /// Open a span for a call to `endpoint`, recording `params` only if given
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn new(endpoint: &str, params: Option<&str>) -> Self {
+        #[cfg(feature = "tracing")]
+        {
+            use tracing::field::Empty;
+            let span = tracing::info_span!(
+                "cufinder_request",
+                endpoint,
+                attempt = Empty,
+                status = Empty,
+                latency_ms = Empty,
+                credit_count = Empty,
+                cache = Empty,
+                error = Empty,
+                params = Empty,
+            );
+            if let Some(params) = params {
+                span.record("params", params);
+            }
+            Self { span }
+        }
+        #[cfg(not(feature = "tracing"))]
+        Self {}
+    }
+
+    /// Run `future` inside the span
+    pub(crate) async fn run<F: Future>(&self, future: F) -> F::Output {
+        #[cfg(feature = "tracing")]
+        {
+            use tracing::Instrument;
+            future.instrument(self.span.clone()).await
+        }
+        #[cfg(not(feature = "tracing"))]
+        future.await
+    }
+
+    /// Record the number of the attempt being sent, starting at 0
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn record_attempt(&self, attempt: u32) {
+        #[cfg(feature = "tracing")]
+        self.span.record("attempt", attempt);
+    }
+
+    /// Record the HTTP status of the latest attempt
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn record_status(&self, status: u16) {
+        #[cfg(feature = "tracing")]
+        self.span.record("status", status);
+    }
+
+    /// Record the outcome of the call
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn finish(
+        &self,
+        cache: CacheOutcome,
+        usage: Option<Usage>,
+        error: Option<ErrorKind>,
+        latency: Duration,
+    ) {
+        #[cfg(feature = "tracing")]
+        {
+            self.span.record("cache", cache.as_str());
+            self.span.record("latency_ms", latency.as_millis() as u64);
+            if let Some(credits) = usage.and_then(|usage| usage.credit_count) {
+                self.span.record("credit_count", credits);
+            }
+            if let Some(error) = error {
+                self.span.record("error", error.as_str());
+            }
+        }
+    }
+}
+
+/// Mirror retries, rate-limit waits and finished attempts as `tracing` events
+#[cfg(feature = "tracing")]
+pub(crate) fn on_event(event: &Event) {
+    match event {
+        Event::RequestFinished {
+            endpoint,
+            attempt,
+            latency,
+            error,
+        } => {
+            tracing::debug!(
+                endpoint = endpoint.as_str(),
+                attempt,
+                latency_ms = latency.as_millis() as u64,
+                error = error.as_ref().map(ErrorKind::as_str),
+                "CUFinder attempt finished"
+            );
+        }
+        Event::RetryScheduled {
+            endpoint,
+            attempt,
+            delay,
+            error,
+        } => {
+            tracing::warn!(
+                endpoint = endpoint.as_str(),
+                attempt,
+                delay_ms = delay.as_millis() as u64,
+                error = error.as_str(),
+                "retrying CUFinder request"
+            );
+        }
+        Event::RateLimitWait { endpoint, wait } => {
+            tracing::debug!(
+                endpoint = endpoint.as_str(),
+                wait_ms = wait.as_millis() as u64,
+                "waiting for CUFinder rate limit"
+            );
+        }
+        Event::AuditWriteFailed {
+            endpoint,
+            error,
+            message,
+        } => {
+            tracing::error!(
+                endpoint = endpoint.as_str(),
+                error = error.as_str(),
+                message = message.as_str(),
+                "failed to write CUFinder audit record"
+            );
+        }
+        _ => {}
+    }
+}
@@ -1,4 +1,5 @@
-use serde::{Deserialize, Serialize};
+use crate::{dates::PartialDate, de};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 
 /// Base response structure for all CUFinder API responses
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,6 +13,55 @@ pub struct BaseResponse {
     pub confidence_level: Option<i32>,
 }
 
+/// Access to JSON keys returned by the API that the SDK does not model yet.
+///
+/// Every response and nested model keeps unrecognized keys in its `extra` map,
+/// so newly added API data is usable before the SDK is updated.
+pub trait ExtraFields {
+    /// Unrecognized keys captured during deserialization
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value>;
+
+    /// Deserialize an unrecognized key as `T`, or `None` if it is missing or has a different shape
+    fn get_extra<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.extra().get(key).and_then(|value| T::deserialize(value).ok())
+    }
+}
+
+macro_rules! impl_extra_fields {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl ExtraFields for $ty {
+                fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+                    &self.extra
+                }
+            }
+        )*
+    };
+}
+
+/// Common accessors shared by every endpoint response
+pub trait ApiResponse {
+    /// The flattened base fields
+    fn base(&self) -> &BaseResponse;
+
+    /// Credits charged for the call, zero if not reported
+    fn credit_count(&self) -> i32 {
+        self.base().credit_count.unwrap_or(0)
+    }
+}
+
+macro_rules! impl_api_response {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl ApiResponse for $ty {
+                fn base(&self) -> &BaseResponse {
+                    &self.base
+                }
+            }
+        )*
+    };
+}
+
 /// Company social media information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompanySocial {
@@ -20,6 +70,8 @@ pub struct CompanySocial {
     pub twitter: Option<String>,
     pub youtube: Option<String>,
     pub instagram: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Company employee information
@@ -27,6 +79,8 @@ pub struct CompanySocial {
 pub struct CompanyEmployees {
     pub range: Option<String>,
     pub count: Option<i32>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Company main location
@@ -40,6 +94,8 @@ pub struct MainLocation {
     pub continent: Option<String>,
     #[serde(rename = "postal_code")]
     pub postal_code: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Company information model
@@ -73,6 +129,8 @@ pub struct Company {
     #[serde(rename = "zip_code")]
     pub zip_code: Option<String>,
     pub address: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Current job information
@@ -81,6 +139,8 @@ pub struct CurrentJob {
     pub title: Option<String>,
     pub role: Option<String>,
     pub level: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 
@@ -90,6 +150,8 @@ pub struct PersonLocation {
     pub country: Option<String>,
     pub state: Option<String>,
     pub city: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Person social media information
@@ -103,6 +165,73 @@ pub struct PersonSocial {
     pub twitter: Option<String>,
     pub facebook: Option<String>,
     pub github: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Work experience entry on a person profile
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Experience {
+    #[serde(default, alias = "company_name", deserialize_with = "de::lenient_string")]
+    pub company: Option<String>,
+    #[serde(default, alias = "company_linkedin", deserialize_with = "de::lenient_string")]
+    pub company_linkedin_url: Option<String>,
+    #[serde(default, alias = "job_title", alias = "position", deserialize_with = "de::lenient_string")]
+    pub title: Option<String>,
+    #[serde(default, deserialize_with = "de::lenient_string")]
+    pub location: Option<String>,
+    #[serde(default, deserialize_with = "de::lenient_string")]
+    pub description: Option<String>,
+    #[serde(default, alias = "start", alias = "starts_at", deserialize_with = "de::lenient_string")]
+    pub start_date: Option<String>,
+    #[serde(default, alias = "end", alias = "ends_at", deserialize_with = "de::lenient_string")]
+    pub end_date: Option<String>,
+    #[serde(default, alias = "current", alias = "is_current_job", deserialize_with = "de::lenient_bool")]
+    pub is_current: Option<bool>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Education entry on a person profile
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Education {
+    #[serde(default, alias = "school_name", alias = "institution", deserialize_with = "de::lenient_string")]
+    pub school: Option<String>,
+    #[serde(default, alias = "school_linkedin", deserialize_with = "de::lenient_string")]
+    pub school_linkedin_url: Option<String>,
+    #[serde(default, alias = "degree_name", deserialize_with = "de::lenient_string")]
+    pub degree: Option<String>,
+    #[serde(default, alias = "field", alias = "major", deserialize_with = "de::lenient_string")]
+    pub field_of_study: Option<String>,
+    #[serde(default, deserialize_with = "de::lenient_string")]
+    pub grade: Option<String>,
+    #[serde(default, deserialize_with = "de::lenient_string")]
+    pub description: Option<String>,
+    #[serde(default, alias = "start", alias = "starts_at", deserialize_with = "de::lenient_string")]
+    pub start_date: Option<String>,
+    #[serde(default, alias = "end", alias = "ends_at", deserialize_with = "de::lenient_string")]
+    pub end_date: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Certification entry on a person profile
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Certification {
+    #[serde(default, alias = "title", deserialize_with = "de::lenient_string")]
+    pub name: Option<String>,
+    #[serde(default, alias = "issuer", alias = "organization", deserialize_with = "de::lenient_string")]
+    pub authority: Option<String>,
+    #[serde(default, deserialize_with = "de::lenient_string")]
+    pub license_number: Option<String>,
+    #[serde(default, deserialize_with = "de::lenient_string")]
+    pub url: Option<String>,
+    #[serde(default, alias = "issued_at", alias = "issue_date", deserialize_with = "de::lenient_string")]
+    pub start_date: Option<String>,
+    #[serde(default, alias = "expires_at", alias = "expiration_date", deserialize_with = "de::lenient_string")]
+    pub end_date: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Person information model
@@ -116,18 +245,143 @@ pub struct Person {
     pub full_name: Option<String>,
     pub logo: Option<String>,
     pub overview: Option<String>,
-    pub experience: Option<serde_json::Value>,
+    #[serde(default, deserialize_with = "de::lenient_vec")]
+    pub experience: Option<Vec<Experience>>,
     pub connections: Option<serde_json::Value>,
     pub interests: Option<Vec<String>>,
     pub skills: Option<Vec<String>>,
-    pub educations: Option<Vec<serde_json::Value>>,
-    pub experiences: Option<Vec<serde_json::Value>>,
-    pub certifications: Option<Vec<serde_json::Value>>,
+    #[serde(default, deserialize_with = "de::lenient_vec")]
+    pub educations: Option<Vec<Education>>,
+    #[serde(default, deserialize_with = "de::lenient_vec")]
+    pub experiences: Option<Vec<Experience>>,
+    #[serde(default, deserialize_with = "de::lenient_vec")]
+    pub certifications: Option<Vec<Certification>>,
     pub company: Option<Company>,
     pub location: Option<PersonLocation>,
     #[serde(rename = "current_job")]
     pub current_job: Option<CurrentJob>,
     pub social: Option<PersonSocial>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+fn is_present(value: &str) -> bool {
+    matches!(value.trim().to_ascii_lowercase().as_str(), "present" | "current" | "now")
+}
+
+impl Experience {
+    /// Parsed start date
+    pub fn start(&self) -> Option<PartialDate> {
+        self.start_date.as_deref().and_then(PartialDate::parse)
+    }
+
+    /// Parsed end date, `None` for ongoing roles
+    pub fn end(&self) -> Option<PartialDate> {
+        self.end_date.as_deref().and_then(PartialDate::parse)
+    }
+
+    /// Whether this is an ongoing role, using the explicit flag when present
+    /// and otherwise a missing or `Present` end date
+    pub fn is_current_role(&self) -> bool {
+        match self.is_current {
+            Some(current) => current,
+            None => self.end_date.as_deref().is_none_or(is_present),
+        }
+    }
+
+    /// Length of the role in months, measuring ongoing roles up to `as_of`
+    pub fn duration_months(&self, as_of: PartialDate) -> Option<u32> {
+        let start = self.start()?;
+        let end = if self.is_current_role() { as_of } else { self.end()? };
+        Some(start.months_until(&end))
+    }
+
+    fn is_at(&self, company: &str) -> bool {
+        self.company
+            .as_deref()
+            .is_some_and(|c| c.trim().eq_ignore_ascii_case(company.trim()))
+    }
+}
+
+impl Person {
+    /// Connection count, reading strings such as `"500+"` or `"1,200"` as their number
+    pub fn connection_count(&self) -> Option<i32> {
+        self.connections.as_ref().and_then(de::count)
+    }
+
+    /// All experience entries, from both `experiences` and `experience`
+    pub fn experience_history(&self) -> impl Iterator<Item = &Experience> {
+        self.experiences
+            .iter()
+            .chain(self.experience.iter())
+            .flatten()
+    }
+
+    /// The most recently started ongoing role
+    pub fn current_role(&self) -> Option<&Experience> {
+        self.experience_history()
+            .filter(|e| e.is_current_role())
+            .max_by_key(|e| e.start())
+    }
+
+    /// Distinct past employers, most recent first, excluding current employers
+    pub fn previous_employers(&self) -> Vec<&str> {
+        let current: Vec<&str> = self
+            .experience_history()
+            .filter(|e| e.is_current_role())
+            .filter_map(|e| e.company.as_deref())
+            .collect();
+
+        let mut past: Vec<&Experience> = self
+            .experience_history()
+            .filter(|e| !e.is_current_role())
+            .collect();
+        past.sort_by_key(|e| std::cmp::Reverse(e.end().or(e.start())));
+
+        let mut employers: Vec<&str> = Vec::new();
+        for company in past.into_iter().filter_map(|e| e.company.as_deref()) {
+            let seen = |c: &&str| c.eq_ignore_ascii_case(company);
+            if !current.iter().any(seen) && !employers.iter().any(seen) {
+                employers.push(company);
+            }
+        }
+        employers
+    }
+
+    /// Total months spent at `company` up to today, see [`Person::tenure_at_as_of`]
+    pub fn tenure_at(&self, company: &str) -> Option<u32> {
+        self.tenure_at_as_of(company, PartialDate::today())
+    }
+
+    /// Total months spent at `company` up to `as_of`, counting overlapping roles once.
+    /// Returns `None` if no role at the company has a usable start date.
+    pub fn tenure_at_as_of(&self, company: &str, as_of: PartialDate) -> Option<u32> {
+        let mut spans: Vec<(i32, i32)> = self
+            .experience_history()
+            .filter(|e| e.is_at(company))
+            .filter_map(|e| {
+                let start = e.start()?.month_index();
+                let end = if e.is_current_role() { as_of } else { e.end()? };
+                Some((start, end.month_index().max(start)))
+            })
+            .collect();
+        if spans.is_empty() {
+            return None;
+        }
+
+        spans.sort_unstable();
+        let mut total = 0;
+        let (mut lo, mut hi) = spans[0];
+        for &(start, end) in &spans[1..] {
+            if start > hi {
+                total += hi - lo;
+                lo = start;
+            }
+            hi = hi.max(end);
+        }
+        total += hi - lo;
+        Some(total as u32)
+    }
 }
 
 // Response types for each service
@@ -136,6 +390,8 @@ pub struct CufResponse {
     #[serde(flatten)]
     pub base: BaseResponse,
     pub domain: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -144,6 +400,8 @@ pub struct LcufResponse {
     pub base: BaseResponse,
     #[serde(rename = "linkedin_url")]
     pub linkedin_url: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -152,6 +410,8 @@ pub struct DtcResponse {
     pub base: BaseResponse,
     #[serde(rename = "company_name")]
     pub company_name: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -159,6 +419,8 @@ pub struct DteResponse {
     #[serde(flatten)]
     pub base: BaseResponse,
     pub emails: Vec<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -166,6 +428,8 @@ pub struct NtpResponse {
     #[serde(flatten)]
     pub base: BaseResponse,
     pub phones: Vec<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -211,6 +475,8 @@ pub struct RelPerson {
     pub company_state: Option<String>,
     #[serde(rename = "company_city")]
     pub company_city: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -218,6 +484,8 @@ pub struct RelResponse {
     #[serde(flatten)]
     pub base: BaseResponse,
     pub person: RelPerson,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -242,6 +510,8 @@ pub struct FclCompany {
     pub logo_url: Option<String>,
     #[serde(rename = "followers_count")]
     pub followers_count: Option<i32>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -249,6 +519,8 @@ pub struct FclResponse {
     #[serde(flatten)]
     pub base: BaseResponse,
     pub companies: Vec<FclCompany>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -261,6 +533,8 @@ pub struct ElfFundraising {
     pub funding_money_raised: Option<String>,
     #[serde(rename = "funding_last_round_investors_url")]
     pub funding_last_round_investors_url: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -269,6 +543,8 @@ pub struct ElfResponse {
     pub base: BaseResponse,
     #[serde(rename = "fundraising_info")]
     pub fundraising: ElfFundraising,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -277,6 +553,8 @@ pub struct CarResponse {
     pub base: BaseResponse,
     #[serde(rename = "annual_revenue")]
     pub revenue: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -284,6 +562,8 @@ pub struct FccResponse {
     #[serde(flatten)]
     pub base: BaseResponse,
     pub subsidiaries: Vec<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -291,6 +571,8 @@ pub struct FtsResponse {
     #[serde(flatten)]
     pub base: BaseResponse,
     pub technologies: Vec<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -336,6 +618,8 @@ pub struct EppPerson {
     pub company_state: Option<String>,
     #[serde(rename = "company_city")]
     pub company_city: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -343,6 +627,8 @@ pub struct EppResponse {
     #[serde(flatten)]
     pub base: BaseResponse,
     pub person: EppPerson,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -351,6 +637,8 @@ pub struct FweResponse {
     pub base: BaseResponse,
     #[serde(rename = "work_email")]
     pub email: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -398,6 +686,8 @@ pub struct TepPerson {
     pub company_city: Option<String>,
     pub email: Option<String>,
     pub phone: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -405,6 +695,8 @@ pub struct TepResponse {
     #[serde(flatten)]
     pub base: BaseResponse,
     pub person: TepPerson,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -430,6 +722,8 @@ pub struct EncCompany {
     pub logo_url: Option<String>,
     #[serde(rename = "followers_count")]
     pub followers_count: Option<i32>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -437,6 +731,8 @@ pub struct EncResponse {
     #[serde(flatten)]
     pub base: BaseResponse,
     pub company: EncCompany,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -444,6 +740,8 @@ pub struct CecResponse {
     #[serde(flatten)]
     pub base: BaseResponse,
     pub countries: serde_json::Value,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -457,6 +755,8 @@ pub struct CloLocation {
     pub line2: Option<String>,
     pub latitude: Option<String>,
     pub longitude: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -464,6 +764,8 @@ pub struct CloResponse {
     #[serde(flatten)]
     pub base: BaseResponse,
     pub locations: Vec<CloLocation>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -471,6 +773,8 @@ pub struct CseResponse {
     #[serde(flatten)]
     pub base: BaseResponse,
     pub companies: Vec<Company>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -478,6 +782,8 @@ pub struct PseResponse {
     #[serde(flatten)]
     pub base: BaseResponse,
     pub peoples: Vec<Person>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -485,6 +791,8 @@ pub struct LbsResponse {
     #[serde(flatten)]
     pub base: BaseResponse,
     pub companies: Vec<Company>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -492,6 +800,8 @@ pub struct BcdResponse {
     #[serde(flatten)]
     pub base: BaseResponse,
     pub customers: Vec<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -500,6 +810,8 @@ pub struct CcpResponse {
     pub base: BaseResponse,
     #[serde(rename = "careers_page_url")]
     pub careers_page_url: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -508,6 +820,8 @@ pub struct IscResponse {
     pub base: BaseResponse,
     #[serde(rename = "is_saas")]
     pub is_saas: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -516,6 +830,8 @@ pub struct CbcResponse {
     pub base: BaseResponse,
     #[serde(rename = "business_type")]
     pub business_type: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -524,6 +840,8 @@ pub struct CscResponse {
     pub base: BaseResponse,
     #[serde(rename = "mission_statement")]
     pub mission_statement: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -536,6 +854,8 @@ pub struct CsnSnapshotInfo {
     pub target_personas: Option<Vec<String>>,
     #[serde(rename = "value_proposition")]
     pub value_proposition: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -544,6 +864,8 @@ pub struct CsnResponse {
     pub base: BaseResponse,
     #[serde(rename = "company_snapshot")]
     pub company_snapshot: CsnSnapshotInfo,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -551,6 +873,8 @@ pub struct NaoResponse {
     #[serde(flatten)]
     pub base: BaseResponse,
     pub phone: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -558,6 +882,8 @@ pub struct NaaResponse {
     #[serde(flatten)]
     pub base: BaseResponse,
     pub address: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -599,6 +925,8 @@ pub struct CefEmployee {
     pub company_state: Option<String>,
     #[serde(rename = "company_city")]
     pub company_city: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -606,6 +934,8 @@ pub struct CefResponse {
     #[serde(flatten)]
     pub base: BaseResponse,
     pub employees: Vec<CefEmployee>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -613,6 +943,8 @@ pub struct NacResponse {
     #[serde(flatten)]
     pub base: BaseResponse,
     pub company: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -649,6 +981,8 @@ pub struct CaaActivity {
     pub activity_top_comments: Option<Vec<String>>,
     #[serde(rename = "activity_videos")]
     pub activity_videos: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -656,11 +990,15 @@ pub struct CaaResponse {
     #[serde(flatten)]
     pub base: BaseResponse,
     pub activities: Vec<CaaActivity>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CjaCompanyEmployees {
     pub range: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -668,6 +1006,8 @@ pub struct CjaCompanyMainLocation {
     pub country: Option<String>,
     pub state: Option<String>,
     pub city: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -687,6 +1027,8 @@ pub struct CjaCompany {
     pub funding_amount: Option<String>,
     #[serde(rename = "main_location")]
     pub main_location: Option<CjaCompanyMainLocation>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -700,12 +1042,16 @@ pub struct CjaJob {
     pub posted_at: Option<String>,
     #[serde(rename = "posted_at_text")]
     pub posted_at_text: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CjaJobItem {
     pub company: CjaCompany,
     pub job: CjaJob,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -714,6 +1060,49 @@ pub struct CjaResponse {
     pub base: BaseResponse,
     #[serde(default)]
     pub jobs: Vec<CjaJobItem>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Company headcount ranges accepted by search filters such as `employee_size`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EmployeeSize {
+    From1To10,
+    From11To50,
+    From51To200,
+    From201To500,
+    From501To1000,
+    From1001To5000,
+    From5001To10000,
+    Over10000,
+}
+
+impl EmployeeSize {
+    /// The range as sent to the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::From1To10 => "1-10",
+            Self::From11To50 => "11-50",
+            Self::From51To200 => "51-200",
+            Self::From201To500 => "201-500",
+            Self::From501To1000 => "501-1000",
+            Self::From1001To5000 => "1001-5000",
+            Self::From5001To10000 => "5001-10000",
+            Self::Over10000 => "10001+",
+        }
+    }
+}
+
+impl AsRef<str> for EmployeeSize {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl std::fmt::Display for EmployeeSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 // Parameter types for each service
@@ -976,4 +1365,24 @@ pub struct CjaParams {
     #[serde(rename = "annual_revenue_max")]
     pub annual_revenue_max: Option<i32>,
     pub page: Option<i32>,
-}
\ No newline at end of file
+}
+
+impl_extra_fields!(
+    CompanySocial, CompanyEmployees, MainLocation, Company, CurrentJob, PersonLocation,
+    PersonSocial, Experience, Education, Certification, Person, CufResponse, LcufResponse,
+    DtcResponse, DteResponse, NtpResponse, RelPerson, RelResponse, FclCompany, FclResponse,
+    ElfFundraising, ElfResponse, CarResponse, FccResponse, FtsResponse, EppPerson, EppResponse,
+    FweResponse, TepPerson, TepResponse, EncCompany, EncResponse, CecResponse, CloLocation,
+    CloResponse, CseResponse, PseResponse, LbsResponse, BcdResponse, CcpResponse, IscResponse,
+    CbcResponse, CscResponse, CsnSnapshotInfo, CsnResponse, NaoResponse, NaaResponse,
+    CefEmployee, CefResponse, NacResponse, CaaActivity, CaaResponse, CjaCompanyEmployees,
+    CjaCompanyMainLocation, CjaCompany, CjaJob, CjaJobItem, CjaResponse,
+);
+
+impl_api_response!(
+    CufResponse, LcufResponse, DtcResponse, DteResponse, NtpResponse, RelResponse, FclResponse,
+    ElfResponse, CarResponse, FccResponse, FtsResponse, EppResponse, FweResponse, TepResponse,
+    EncResponse, CecResponse, CloResponse, CseResponse, PseResponse, LbsResponse, BcdResponse,
+    CcpResponse, IscResponse, CbcResponse, CscResponse, CsnResponse, NaoResponse, NaaResponse,
+    CefResponse, NacResponse, CaaResponse, CjaResponse,
+);
@@ -0,0 +1,102 @@
+mod common;
+
+use common::create_test_sdk;
+use cufinder_rust::{
+    CseParams, CufinderApi, CufinderError, DtcResponse, ErrorKind, Result, api::FakeCufinder,
+};
+use mockito::Server;
+use serde_json::json;
+
+/// Application code written against the trait rather than the SDK
+async fn company_names(api: &impl CufinderApi, domains: &[&str]) -> Result<Vec<String>> {
+    let mut names = Vec::new();
+    for domain in domains {
+        names.push(api.dtc(domain).await?.company_name);
+    }
+    Ok(names)
+}
+
+fn dtc_response(name: &str) -> Result<DtcResponse> {
+    Ok(serde_json::from_value(
+        json!({ "company_name": name, "credit_count": 1 }),
+    )?)
+}
+
+#[tokio::test]
+async fn test_fake_returns_scripted_responses_and_records_calls() {
+    let fake = FakeCufinder::new();
+    fake.on_dtc(|domain| match domain {
+        "acme.com" => dtc_response("Acme"),
+        _ => Err(CufinderError::ApiError {
+            status: 404,
+            message: "Not found".to_string(),
+        }),
+    });
+
+    let names = company_names(&fake, &["acme.com"]).await.unwrap();
+    assert_eq!(names, vec!["Acme"]);
+
+    let error = company_names(&fake, &["acme.com", "missing.com"])
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Api);
+
+    assert_eq!(fake.call_count("dtc"), 3);
+    assert_eq!(
+        fake.calls_to("dtc"),
+        vec![
+            json!({ "company_website": "acme.com" }),
+            json!({ "company_website": "acme.com" }),
+            json!({ "company_website": "missing.com" }),
+        ]
+    );
+
+    fake.clear_calls();
+    assert!(fake.calls().is_empty());
+}
+
+#[tokio::test]
+async fn test_fake_records_struct_and_multi_argument_calls() {
+    let fake = FakeCufinder::new();
+    fake.on_cse(|params| {
+        assert_eq!(params.country.as_deref(), Some("US"));
+        Ok(serde_json::from_value(json!({ "companies": [] }))?)
+    });
+
+    let params = CseParams {
+        country: Some("US".to_string()),
+        ..Default::default()
+    };
+    fake.cse(params).await.unwrap();
+
+    // Unscripted methods fail but are still recorded
+    let error = fake.tep("Jane Doe", "Acme").await.unwrap_err();
+    assert!(error.to_string().contains("no response scripted for tep"));
+
+    let calls = fake.calls();
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[0].method, "cse");
+    assert_eq!(calls[0].args["params"]["country"], "US");
+    assert_eq!(calls[1].method, "tep");
+    assert_eq!(
+        calls[1].args,
+        json!({ "full_name": "Jane Doe", "company": "Acme" })
+    );
+}
+
+#[tokio::test]
+async fn test_sdk_implements_api() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/dtc")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "company_name": "TechCorp", "credit_count": 1 }).to_string())
+        .create_async()
+        .await;
+
+    let sdk = create_test_sdk(&server.url(), 0);
+    let names = company_names(&sdk, &["techcorp.com"]).await.unwrap();
+    assert_eq!(names, vec!["TechCorp"]);
+    mock.assert_async().await;
+}
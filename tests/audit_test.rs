@@ -0,0 +1,293 @@
+#![cfg(feature = "audit")]
+
+mod common;
+
+use common::create_test_sdk;
+use cufinder_rust::{
+    ErrorKind, Event,
+    audit::{AuditConfig, AuditLog, AuditOutcome, AuditRecord, GENESIS_HASH, Redaction},
+};
+use mockito::Server;
+use serde_json::json;
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+#[tokio::test]
+async fn test_audit_log_records_personal_data_lookups_redacted() {
+    let mut server = Server::new_async().await;
+    server
+        .mock("POST", "/rel")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "data": { "person": {}, "credit_count": 1 } }).to_string())
+        .create_async()
+        .await;
+    server
+        .mock("POST", "/tep")
+        .with_status(404)
+        .with_body("not found")
+        .create_async()
+        .await;
+    server
+        .mock("POST", "/dtc")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "company_name": "TechCorp" }).to_string())
+        .create_async()
+        .await;
+
+    let path = temp_log("sdk");
+    let config = AuditConfig::new().salt("pepper").names(Redaction::Mask);
+    let sdk = create_test_sdk(&server.url(), 0)
+        .with_audit(AuditLog::open(&path, config.clone()).unwrap());
+
+    sdk.tagged("compliance")
+        .rel(" John.Doe@Example.com")
+        .await
+        .unwrap();
+    sdk.tep("John Doe", "TechCorp").await.unwrap_err();
+    sdk.dtc("techcorp.com").await.unwrap();
+
+    let records = read_records(&path);
+    assert_eq!(records.len(), 2, "/dtc is not audited");
+    assert_eq!(records[0].endpoint, "/rel");
+    assert_eq!(records[0].tag.as_deref(), Some("compliance"));
+    assert_eq!(records[0].outcome, AuditOutcome::Success);
+    assert_eq!(
+        records[0].params["email"],
+        config.redact("email", "john.doe@example.com").unwrap()
+    );
+    assert!(records[0].params["email"].starts_with("sha256:"));
+    assert_eq!(records[1].params["full_name"], "J*** D***");
+    assert_eq!(records[1].params["company"], "TechCorp");
+    assert_eq!(
+        records[1].outcome,
+        AuditOutcome::Error {
+            kind: ErrorKind::Api
+        }
+    );
+
+    let raw = std::fs::read_to_string(&path).unwrap();
+    assert!(!raw.contains("Example.com") && !raw.contains("John"));
+
+    let verification = AuditLog::verify(&path).unwrap();
+    assert!(verification.is_intact(), "{:?}", verification.problems);
+    assert!(verification.anchored);
+    assert_eq!(verification.records, 2);
+    remove_logs(&path);
+}
+
+#[test]
+fn test_redaction_modes() {
+    let config = AuditConfig::new()
+        .emails(Redaction::Mask)
+        .linkedin_urls(Redaction::Mask)
+        .names(Redaction::Remove);
+    assert_eq!(
+        config.redact("email", "jane@example.com").as_deref(),
+        Some("j***@example.com")
+    );
+    assert_eq!(
+        config
+            .redact("linkedin_url", "https://www.linkedin.com/in/janedoe")
+            .as_deref(),
+        Some("https://www.linkedin.com/in/***")
+    );
+    assert_eq!(config.redact("full_name", "Jane Doe"), None);
+    assert_eq!(config.redact("company", "Acme").as_deref(), Some("Acme"));
+
+    let salted = AuditConfig::new().salt("a");
+    assert_eq!(
+        salted.redact("email", "Jane@Example.com"),
+        salted.redact("email", "jane@example.com")
+    );
+    assert_ne!(
+        salted.redact("email", "jane@example.com"),
+        AuditConfig::new()
+            .salt("b")
+            .redact("email", "jane@example.com")
+    );
+}
+
+#[tokio::test]
+async fn test_audit_log_rotates_and_detects_tampering() {
+    let mut server = Server::new_async().await;
+    server
+        .mock("POST", "/rel")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "person": {} }).to_string())
+        .create_async()
+        .await;
+
+    let path = temp_log("rotate");
+    let config = AuditConfig::new().salt("pepper").rotate(400, 2);
+    let sdk = create_test_sdk(&server.url(), 0).with_audit(AuditLog::open(&path, config).unwrap());
+    for i in 0..4 {
+        sdk.rel(&format!("person{}@example.com", i)).await.unwrap();
+    }
+    // Reopening continues the chain
+    let sdk = create_test_sdk(&server.url(), 0).with_audit(
+        AuditLog::open(&path, AuditConfig::new().salt("pepper").rotate(400, 2)).unwrap(),
+    );
+    sdk.rel("last@example.com").await.unwrap();
+
+    let verification = AuditLog::verify(&path).unwrap();
+    assert!(verification.is_intact(), "{:?}", verification.problems);
+    assert_eq!(verification.files.len(), 3);
+    assert!(!verification.anchored, "the oldest file was dropped");
+    let newest = read_records(&path);
+    assert_eq!(newest.last().unwrap().seq, 5);
+    assert_eq!(
+        verification.last_hash.as_ref(),
+        Some(&newest.last().unwrap().hash)
+    );
+
+    let rotated = verification.files[1].clone();
+    let text = std::fs::read_to_string(&rotated).unwrap();
+    let tampered = text.replacen("\"/rel\"", "\"/enc\"", 1);
+    std::fs::write(&rotated, tampered).unwrap();
+
+    let verification = AuditLog::verify(&path).unwrap();
+    assert!(!verification.is_intact());
+    assert_eq!(verification.problems[0].file, rotated);
+    assert_eq!(verification.problems[0].line, 1);
+    assert!(verification.problems[0].reason.contains("hash"));
+    remove_logs(&path);
+}
+
+#[test]
+fn test_verify_detects_removed_records() {
+    let path = temp_log("removed");
+    // Hashing without a salt is refused
+    let error = AuditLog::open(&path, AuditConfig::new()).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Validation);
+    AuditLog::open(
+        &path,
+        AuditConfig::new()
+            .emails(Redaction::Remove)
+            .names(Redaction::Mask)
+            .linkedin_urls(Redaction::Mask),
+    )
+    .unwrap();
+
+    let mut prev_hash = GENESIS_HASH.to_string();
+    let mut lines = Vec::new();
+    for seq in 1..=3 {
+        let mut record = AuditRecord {
+            seq,
+            timestamp: 1_000 * seq,
+            endpoint: "/epp".to_string(),
+            tag: None,
+            params: Default::default(),
+            outcome: AuditOutcome::Success,
+            cache_hit: false,
+            prev_hash,
+            hash: String::new(),
+        };
+        record.hash = record.compute_hash().unwrap();
+        prev_hash = record.hash.clone();
+        lines.push(serde_json::to_string(&record).unwrap());
+    }
+    lines.remove(1);
+    std::fs::write(&path, lines.join("\n") + "\n").unwrap();
+
+    let verification = AuditLog::verify(&path).unwrap();
+    assert_eq!(verification.records, 2);
+    let reasons: Vec<_> = verification
+        .problems
+        .iter()
+        .map(|p| (p.line, p.reason.as_str()))
+        .collect();
+    assert_eq!(
+        reasons,
+        vec![
+            (2, "expected seq 2, found 3"),
+            (2, "prev_hash does not match the previous record"),
+        ]
+    );
+    remove_logs(&path);
+}
+
+#[tokio::test]
+async fn test_audit_write_failures_are_reported() {
+    let mut server = Server::new_async().await;
+    server
+        .mock("POST", "/rel")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "person": {} }).to_string())
+        .create_async()
+        .await;
+
+    // A truncated last line from a crash is left on its own line
+    let path = temp_log("failures");
+    std::fs::write(&path, "{\"seq\": 1, \"times").unwrap();
+    let config = AuditConfig::new().salt("pepper");
+    let sdk = create_test_sdk(&server.url(), 0)
+        .with_audit(AuditLog::open(&path, config.clone()).unwrap());
+    sdk.rel("jane@example.com").await.unwrap();
+
+    let verification = AuditLog::verify(&path).unwrap();
+    assert_eq!(verification.records, 1);
+    assert_eq!(verification.problems.len(), 1);
+    assert_eq!(verification.problems[0].line, 1);
+
+    // A directory in the way of rotation makes the next write fail
+    let blocker = PathBuf::from(format!("{}.1", path.display()));
+    std::fs::create_dir_all(blocker.join("keep")).unwrap();
+    let config = config.rotate(1, 1);
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let seen = events.clone();
+    let sdk = create_test_sdk(&server.url(), 0)
+        .with_observer(move |event: &Event| seen.lock().unwrap().push(event.clone()))
+        .with_audit(AuditLog::open(&path, config.clone()).unwrap());
+    sdk.rel("jane@example.com").await.unwrap();
+    let failures: Vec<_> = events
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|event| match event {
+            Event::AuditWriteFailed {
+                endpoint, error, ..
+            } => Some((endpoint.clone(), *error)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(failures, vec![("/rel".to_string(), ErrorKind::Io)]);
+
+    let strict = create_test_sdk(&server.url(), 0)
+        .with_audit(AuditLog::open(&path, config.strict(true)).unwrap());
+    let error = strict.rel("jane@example.com").await.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Io);
+
+    std::fs::remove_dir_all(&blocker).unwrap();
+    remove_logs(&path);
+}
+
+fn temp_log(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "cufinder-audit-{}-{}.jsonl",
+        name,
+        std::process::id()
+    ));
+    remove_logs(&path);
+    path
+}
+
+fn remove_logs(path: &PathBuf) {
+    let _ = std::fs::remove_file(path);
+    for n in 1..=10 {
+        let _ = std::fs::remove_file(format!("{}.{}", path.display(), n));
+    }
+}
+
+fn read_records(path: &PathBuf) -> Vec<AuditRecord> {
+    std::fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
@@ -0,0 +1,289 @@
+mod common;
+
+use common::create_test_sdk;
+use cufinder_rust::{
+    Client, ClientConfig, CufinderSDK, DtcResponse, ErrorKind,
+    batch::{BatchOptions, EnrichOptions},
+    rate_limit::RateLimiter,
+};
+use futures::{StreamExt, stream};
+use mockito::{Matcher, Server};
+use serde_json::json;
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+#[tokio::test]
+async fn test_batch_preserves_order_and_summarizes() {
+    let mut server = Server::new_async().await;
+    for (domain, credits) in [("a.com", 1), ("b.com", 2), ("d.com", 1)] {
+        server
+            .mock("POST", "/enc")
+            .match_body(Matcher::UrlEncoded("query".to_string(), domain.to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({ "company": { "domain": domain }, "credit_count": credits }).to_string(),
+            )
+            .create_async()
+            .await;
+    }
+    server
+        .mock("POST", "/enc")
+        .match_body(Matcher::UrlEncoded(
+            "query".to_string(),
+            "c.com".to_string(),
+        ))
+        .with_status(404)
+        .with_body("not found")
+        .create_async()
+        .await;
+
+    let sdk = rate_limited_sdk(&server.url(), 0);
+    let domains = vec!["a.com", "b.com", "c.com", "", "d.com"];
+    let output = sdk
+        .batch(
+            domains,
+            BatchOptions::new().concurrency(3),
+            |sdk, domain| async move { sdk.enc(domain).await },
+        )
+        .await;
+
+    let domains: Vec<Option<String>> = output
+        .results
+        .iter()
+        .map(|r| r.as_ref().ok().and_then(|r| r.company.domain.clone()))
+        .collect();
+    assert_eq!(
+        domains,
+        vec![
+            Some("a.com".into()),
+            Some("b.com".into()),
+            None,
+            None,
+            Some("d.com".into())
+        ]
+    );
+
+    let summary = &output.summary;
+    assert_eq!(summary.total, 5);
+    assert_eq!(summary.succeeded, 3);
+    assert_eq!(summary.failed, 2);
+    assert_eq!(summary.failures_by_kind.get(&ErrorKind::Api), Some(&1));
+    assert_eq!(
+        summary.failures_by_kind.get(&ErrorKind::Validation),
+        Some(&1)
+    );
+    assert_eq!(summary.credits_used, 4);
+}
+
+#[tokio::test]
+async fn test_client_retries_retryable_errors() {
+    let mut server = Server::new_async().await;
+    let rate_limited = server
+        .mock("POST", "/dtc")
+        .with_status(429)
+        .with_header("retry-after", "0")
+        .with_body("slow down")
+        .expect(2)
+        .create_async()
+        .await;
+    let success = server
+        .mock("POST", "/dtc")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "company_name": "TechCorp", "credit_count": 1 }).to_string())
+        .expect(1)
+        .create_async()
+        .await;
+
+    let sdk = rate_limited_sdk(&server.url(), 2);
+    let result = sdk.dtc("techcorp.com").await.unwrap();
+    assert_eq!(result.company_name, "TechCorp");
+    rate_limited.assert_async().await;
+    success.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_client_does_not_retry_client_errors() {
+    let mut server = Server::new_async().await;
+    let unauthorized = server
+        .mock("POST", "/dtc")
+        .with_status(401)
+        .with_body("bad key")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let sdk = rate_limited_sdk(&server.url(), 3);
+    let error = sdk.dtc("techcorp.com").await.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Authentication);
+    assert!(!error.is_retryable());
+    unauthorized.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_client_does_not_wait_for_long_retry_after() {
+    let mut server = Server::new_async().await;
+    let rate_limited = server
+        .mock("POST", "/dtc")
+        .with_status(429)
+        .with_header("retry-after", "3600")
+        .with_body("come back in an hour")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let sdk = rate_limited_sdk(&server.url(), 3);
+    let started = Instant::now();
+    let error = sdk.dtc("techcorp.com").await.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::RateLimit);
+    assert!(started.elapsed() < Duration::from_secs(5));
+    rate_limited.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_client_retries_timeouts_only_when_enabled() {
+    // A server that accepts connections and never answers
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let connections = Arc::new(AtomicUsize::new(0));
+    let counter = connections.clone();
+    tokio::spawn(async move {
+        let mut open = Vec::new();
+        while let Ok((socket, _)) = listener.accept().await {
+            counter.fetch_add(1, Ordering::SeqCst);
+            open.push(socket);
+        }
+    });
+
+    let client = |retry_timeouts: bool| {
+        CufinderSDK::with_config(ClientConfig {
+            api_key: "test-api-key".to_string(),
+            base_url: url.clone(),
+            timeout: Duration::from_millis(100),
+            max_retries: 2,
+        })
+        .unwrap()
+        .with_retry_timeouts(retry_timeouts)
+    };
+
+    let error = client(false).dtc("techcorp.com").await.unwrap_err();
+    assert!(error.is_timeout() && !error.is_retryable());
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+
+    client(true).dtc("techcorp.com").await.unwrap_err();
+    assert_eq!(connections.load(Ordering::SeqCst), 4);
+}
+
+#[test]
+fn test_rate_limiter_rejects_invalid_rates() {
+    for rate in [0.0, -1.0, f64::NAN, 1e-300] {
+        let error = RateLimiter::try_new(rate).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::Validation, "{}", rate);
+    }
+    let unlimited = RateLimiter::try_new(f64::INFINITY).unwrap();
+    assert_eq!(unlimited.interval(), Duration::ZERO);
+
+    let sdk = create_test_sdk("http://localhost", 0);
+    let error = sdk.try_with_rate_limit(-1.0).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::Validation);
+    let sdk = create_test_sdk("http://localhost", 0);
+    assert!(sdk.try_with_rate_limit(10.0).is_ok());
+    let client = Client::new(ClientConfig::default()).unwrap();
+    assert!(client.try_with_rate_limit(f64::NAN).is_err());
+}
+
+#[tokio::test]
+async fn test_rate_limiter_spaces_requests() {
+    let limiter = RateLimiter::new(20.0);
+    assert_eq!(limiter.interval(), Duration::from_millis(50));
+
+    let start = Instant::now();
+    for _ in 0..4 {
+        limiter.acquire().await;
+    }
+    assert!(start.elapsed() >= Duration::from_millis(150));
+}
+
+#[tokio::test]
+async fn test_enrich_stream_ordered_and_unordered() {
+    let mut server = Server::new_async().await;
+    server
+        .mock("POST", "/dtc")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "company_name": "TechCorp", "credit_count": 1 }).to_string())
+        .create_async()
+        .await;
+
+    let sdk = rate_limited_sdk(&server.url(), 0);
+    let input = || stream::iter(vec![("slow.com", 80), ("fast.com", 0)]);
+
+    let unordered: Vec<_> = sdk
+        .enrich_stream(input(), EnrichOptions::new().concurrency(2), delayed_dtc)
+        .map(|((domain, _), result)| (domain, result.is_ok()))
+        .collect()
+        .await;
+    assert_eq!(unordered, vec![("fast.com", true), ("slow.com", true)]);
+
+    let ordered: Vec<_> = sdk
+        .enrich_stream(
+            input(),
+            EnrichOptions::new().concurrency(2).ordered(true),
+            delayed_dtc,
+        )
+        .map(|((domain, _), _)| domain)
+        .collect()
+        .await;
+    assert_eq!(ordered, vec!["slow.com", "fast.com"]);
+}
+
+async fn delayed_dtc(
+    sdk: &CufinderSDK,
+    (domain, delay): (&'static str, u64),
+) -> cufinder_rust::Result<DtcResponse> {
+    tokio::time::sleep(Duration::from_millis(delay)).await;
+    sdk.dtc(domain).await
+}
+
+#[tokio::test]
+async fn test_enrich_stream_applies_backpressure_to_unbounded_input() {
+    let mut server = Server::new_async().await;
+    server
+        .mock("POST", "/enc")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "company": { "name": "TechCorp" } }).to_string())
+        .create_async()
+        .await;
+
+    let sdk = rate_limited_sdk(&server.url(), 0);
+    let pulled = AtomicUsize::new(0);
+    let input = stream::iter(0..).inspect(|_| {
+        pulled.fetch_add(1, Ordering::SeqCst);
+    });
+
+    let first: Vec<_> = sdk
+        .enrich_stream(
+            input,
+            EnrichOptions::new().concurrency(3),
+            |sdk, n: u64| async move { sdk.enc(&format!("{n}.com")).await },
+        )
+        .take(2)
+        .collect()
+        .await;
+
+    assert_eq!(first.len(), 2);
+    assert!(pulled.load(Ordering::SeqCst) <= 5);
+}
+
+/// The shared test SDK behind a generous client-side rate limit, so every batch and retry
+/// test also runs its requests through the limiter
+fn rate_limited_sdk(base_url: &str, max_retries: u32) -> CufinderSDK {
+    create_test_sdk(base_url, max_retries).with_rate_limit(1000.0)
+}
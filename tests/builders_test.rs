@@ -0,0 +1,96 @@
+use cufinder_rust::{CjaParams, CseParams, CufinderError, EmployeeSize, LbsParams, PseParams};
+
+#[test]
+fn test_cse_builder_matches_struct_update() {
+    let built = CseParams::builder()
+        .country("US")
+        .industry("Software")
+        .employee_size(EmployeeSize::From51To200)
+        .founded_between(2010, 2020)
+        .annual_revenue_between(1_000_000, 50_000_000)
+        .followers_count_min(1000)
+        .is_school(false)
+        .page(2)
+        .build()
+        .unwrap();
+
+    let manual = CseParams {
+        country: Some("US".to_string()),
+        industry: Some("Software".to_string()),
+        employee_size: Some("51-200".to_string()),
+        founded_after_year: Some(2010),
+        founded_before_year: Some(2020),
+        annual_revenue_min: Some(1_000_000),
+        annual_revenue_max: Some(50_000_000),
+        followers_count_min: Some(1000),
+        is_school: Some(false),
+        page: Some(2),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        serde_urlencoded::to_string(&built).unwrap(),
+        serde_urlencoded::to_string(&manual).unwrap()
+    );
+}
+
+#[test]
+fn test_builder_rejects_inverted_ranges() {
+    let result = CseParams::builder().founded_between(2020, 2010).build();
+    match result {
+        Err(CufinderError::ValidationError(msg)) => {
+            assert!(msg.contains("founded_after_year"));
+            assert!(msg.contains("founded_before_year"));
+        }
+        other => panic!("expected validation error, got {:?}", other),
+    }
+
+    assert!(CjaParams::builder().funding_amount_min(500).funding_amount_max(100).build().is_err());
+    assert!(PseParams::builder().company_annual_revenue_between(10, 5).build().is_err());
+    assert!(CseParams::builder().followers_count_min(-1).build().is_err());
+    assert!(CseParams::builder().followers_count_between(5, 5).build().is_ok());
+}
+
+#[test]
+fn test_builder_rejects_empty_values() {
+    match LbsParams::builder().name("coffee").city("  ").build() {
+        Err(CufinderError::ValidationError(msg)) => assert!(msg.contains("city")),
+        other => panic!("expected validation error, got {:?}", other),
+    }
+
+    assert!(CseParams::builder().products_services(Vec::<String>::new()).build().is_err());
+    assert!(CseParams::builder().products_services(["crm", ""]).build().is_err());
+    assert!(LbsParams::builder().page(0).build().is_err());
+}
+
+#[test]
+fn test_pse_builder() {
+    let params = PseParams::builder()
+        .job_title_role("engineering")
+        .job_title_level("director")
+        .company_name("TechCorp")
+        .company_employee_size("1001-5000")
+        .company_products_services(["analytics", "crm"])
+        .build()
+        .unwrap();
+
+    assert_eq!(params.job_title_role.as_deref(), Some("engineering"));
+    assert_eq!(params.company_employee_size.as_deref(), Some("1001-5000"));
+    assert_eq!(
+        params.company_products_services,
+        Some(vec!["analytics".to_string(), "crm".to_string()])
+    );
+    assert_eq!(params.page, None);
+}
+
+#[test]
+fn test_validate_on_existing_params() {
+    let params = CjaParams {
+        annual_revenue_min: Some(10),
+        annual_revenue_max: Some(1),
+        ..Default::default()
+    };
+    assert!(params.validate().is_err());
+    assert!(CjaParams::default().validate().is_ok());
+    assert_eq!(EmployeeSize::Over10000.to_string(), "10001+");
+}
@@ -0,0 +1,296 @@
+mod common;
+
+use common::create_test_sdk;
+use cufinder_rust::{
+    ClientConfig, CufinderSDK, DtcResponse,
+    bulk::{BulkJob, BulkProgress, CheckpointStatus},
+};
+use mockito::{Matcher, Server};
+use serde_json::json;
+use std::{
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+fn checkpoint_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "cufinder-bulk-{}-{}.jsonl",
+        name,
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[tokio::test]
+async fn test_bulk_job_resumes_from_checkpoint() {
+    let mut server = Server::new_async().await;
+    let ok = |domain: &str| {
+        json!({ "company_name": domain.to_uppercase(), "credit_count": 1 }).to_string()
+    };
+    let a = server
+        .mock("POST", "/dtc")
+        .match_body(Matcher::UrlEncoded(
+            "company_website".into(),
+            "a.com".into(),
+        ))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(ok("a.com"))
+        .expect(1)
+        .create_async()
+        .await;
+    let b_failure = server
+        .mock("POST", "/dtc")
+        .match_body(Matcher::UrlEncoded(
+            "company_website".into(),
+            "b.com".into(),
+        ))
+        .with_status(503)
+        .with_body("unavailable")
+        .expect(2)
+        .create_async()
+        .await;
+    let c = server
+        .mock("POST", "/dtc")
+        .match_body(Matcher::UrlEncoded(
+            "company_website".into(),
+            "c.com".into(),
+        ))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(ok("c.com"))
+        .expect(1)
+        .create_async()
+        .await;
+
+    let sdk = create_test_sdk(&server.url(), 0);
+    let path = checkpoint_path("resume");
+
+    // First run: b.com fails twice within the run, backing off, and is exhausted
+    let mut job = BulkJob::open(&path).unwrap().max_attempts(2);
+    let inputs = vec!["a.com".to_string(), "b.com".to_string()];
+    let report = job
+        .run(
+            &sdk,
+            inputs,
+            |sdk, domain| async move { sdk.dtc(&domain).await },
+        )
+        .await
+        .unwrap();
+    assert_eq!(report.skipped, 0);
+    assert_eq!(report.summary.succeeded, 1);
+    assert_eq!(report.summary.failed, 2);
+    assert_eq!(
+        job.progress(),
+        BulkProgress {
+            completed: 1,
+            retryable: 0,
+            exhausted: 1
+        }
+    );
+    drop(job);
+
+    // Restart: completed and exhausted inputs are skipped, new ones run
+    let mut job = BulkJob::open(&path).unwrap().max_attempts(2);
+    let inputs = vec![
+        "a.com".to_string(),
+        "b.com".to_string(),
+        "c.com".to_string(),
+        "c.com".to_string(),
+    ];
+    let report = job
+        .run(
+            &sdk,
+            inputs,
+            |sdk, domain| async move { sdk.dtc(&domain).await },
+        )
+        .await
+        .unwrap();
+    assert_eq!(report.skipped, 2);
+    assert_eq!(report.summary.total, 1);
+
+    let record = job.record(&"b.com").unwrap().unwrap();
+    assert_eq!(record.status, CheckpointStatus::Failed);
+    assert_eq!(record.attempts, 2);
+    assert_eq!(record.error_kind.as_deref(), Some("api"));
+    assert!(!record.permanent);
+
+    let results = job.results::<DtcResponse>().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results["c.com"].company_name, "C.COM");
+
+    // Raising the limit makes exhausted inputs eligible again
+    let job = BulkJob::open(&path).unwrap().max_attempts(3);
+    assert_eq!(
+        job.progress(),
+        BulkProgress {
+            completed: 2,
+            retryable: 1,
+            exhausted: 0
+        }
+    );
+
+    a.assert_async().await;
+    b_failure.assert_async().await;
+    c.assert_async().await;
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_checkpoint_tolerates_truncated_lines_and_compacts() {
+    let path = checkpoint_path("compact");
+    let record = |attempts: u32, status: &str| {
+        json!({
+            "key": "a.com", "status": status, "attempts": attempts, "result": null,
+            "error": null, "error_kind": null, "timestamp": 0
+        })
+        .to_string()
+    };
+    let contents = format!(
+        "{}\n{}\n{{\"key\": \"b.c",
+        record(1, "failed"),
+        record(2, "completed")
+    );
+    std::fs::write(&path, contents).unwrap();
+
+    let mut job = BulkJob::open(&path).unwrap();
+    assert_eq!(job.progress().completed, 1);
+    assert_eq!(job.records().count(), 1);
+
+    job.compact().unwrap();
+    let lines = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(lines.lines().count(), 1);
+    assert!(lines.contains("\"completed\""));
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn test_bulk_job_does_not_retry_permanent_or_unsent_failures() {
+    let mut server = Server::new_async().await;
+    let missing = server
+        .mock("POST", "/dtc")
+        .match_body(Matcher::UrlEncoded(
+            "company_website".into(),
+            "missing.com".into(),
+        ))
+        .with_status(404)
+        .with_body("not found")
+        .expect(2)
+        .create_async()
+        .await;
+
+    let sdk = create_test_sdk(&server.url(), 0);
+    let path = checkpoint_path("permanent");
+    let inputs = || vec!["missing.com".to_string(), String::new()];
+
+    let mut job = BulkJob::open(&path).unwrap().max_attempts(3);
+    let report = job.run(&sdk, inputs(), dtc).await.unwrap();
+    assert_eq!(report.summary.failed, 2);
+
+    // A not-found result is sent once and is permanent
+    let record = job.record(&"missing.com").unwrap().unwrap();
+    assert_eq!((record.attempts, record.permanent), (1, true));
+    // A validation error never reaches the API and uses up no attempt
+    let record = job.record(&"").unwrap().unwrap();
+    assert_eq!((record.attempts, record.permanent), (0, false));
+    assert_eq!(record.error_kind.as_deref(), Some("validation"));
+    assert_eq!(
+        job.progress(),
+        BulkProgress {
+            completed: 0,
+            retryable: 1,
+            exhausted: 1
+        }
+    );
+
+    let report = job.run(&sdk, inputs(), dtc).await.unwrap();
+    assert_eq!((report.skipped, report.summary.total), (1, 1));
+
+    // Resetting makes permanent failures eligible again, surviving a reopen
+    assert_eq!(job.reset_failed().unwrap(), 2);
+    drop(job);
+    let mut job = BulkJob::open(&path).unwrap();
+    assert_eq!(job.records().count(), 0);
+    let report = job
+        .run(&sdk, vec!["missing.com".to_string()], dtc)
+        .await
+        .unwrap();
+    assert_eq!((report.skipped, report.summary.failed), (0, 1));
+
+    missing.assert_async().await;
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn test_bulk_job_retries_timeouts_up_to_the_attempt_limit() {
+    // A server that accepts connections and never answers
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let connections = Arc::new(AtomicUsize::new(0));
+    let counter = connections.clone();
+    tokio::spawn(async move {
+        let mut open = Vec::new();
+        while let Ok((socket, _)) = listener.accept().await {
+            counter.fetch_add(1, Ordering::SeqCst);
+            open.push(socket);
+        }
+    });
+
+    let sdk = CufinderSDK::with_config(ClientConfig {
+        api_key: "test-api-key".to_string(),
+        base_url: url,
+        timeout: Duration::from_millis(100),
+        max_retries: 0,
+    })
+    .unwrap();
+    let path = checkpoint_path("timeouts");
+    let mut job = BulkJob::open(&path).unwrap().max_attempts(2);
+    let report = job
+        .run(&sdk, vec!["slow.com".to_string()], dtc)
+        .await
+        .unwrap();
+    assert_eq!(report.summary.failed, 2);
+    assert_eq!(connections.load(Ordering::SeqCst), 2);
+
+    let record = job.record(&"slow.com").unwrap().unwrap();
+    assert_eq!((record.attempts, record.permanent), (2, false));
+    assert_eq!(job.progress().exhausted, 1);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn test_bulk_job_counts_client_retries_as_attempts() {
+    let mut server = Server::new_async().await;
+    let unavailable = server
+        .mock("POST", "/dtc")
+        .with_status(503)
+        .with_body("unavailable")
+        .expect(3)
+        .create_async()
+        .await;
+
+    // The client retries twice on its own, which uses up all three attempts in one call
+    let sdk = create_test_sdk(&server.url(), 2);
+    let path = checkpoint_path("client-retries");
+    let mut job = BulkJob::open(&path).unwrap().max_attempts(3);
+    let report = job
+        .run(&sdk, vec!["down.com".to_string()], dtc)
+        .await
+        .unwrap();
+    assert_eq!(report.summary.failed, 1);
+
+    let record = job.record(&"down.com").unwrap().unwrap();
+    assert_eq!((record.attempts, record.permanent), (3, false));
+    assert_eq!(job.progress().exhausted, 1);
+    unavailable.assert_async().await;
+    let _ = std::fs::remove_file(&path);
+}
+
+async fn dtc(sdk: &CufinderSDK, domain: String) -> cufinder_rust::Result<DtcResponse> {
+    sdk.dtc(&domain).await
+}
@@ -0,0 +1,275 @@
+mod common;
+
+use common::create_test_sdk;
+use cufinder_rust::{
+    CacheConfig, CufinderError, DtcParams, ErrorKind,
+    cache::{CachedResponse, ResponseCache},
+    cache_store::{CacheEntry, CacheStore, FileStore, MemoryStore},
+};
+use mockito::Server;
+use serde_json::json;
+use std::{path::PathBuf, time::Duration};
+
+#[tokio::test]
+async fn test_cache_serves_repeated_requests() {
+    let mut server = Server::new_async().await;
+    let dtc = server
+        .mock("POST", "/dtc")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "company_name": "TechCorp", "credit_count": 1 }).to_string())
+        .expect(1)
+        .create_async()
+        .await;
+    let missing = server
+        .mock("POST", "/enc")
+        .with_status(404)
+        .with_body("company not found")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let sdk = create_test_sdk(&server.url(), 0).with_cache(CacheConfig::new());
+
+    let first = sdk.dtc("techcorp.com").await.unwrap();
+    let second = sdk.dtc("techcorp.com").await.unwrap();
+    assert_eq!(first.base.credit_count, Some(1));
+    assert_eq!(second.company_name, "TechCorp");
+    assert_eq!(second.base.credit_count, Some(0));
+
+    for _ in 0..2 {
+        let error = sdk.enc("missing.com").await.unwrap_err();
+        assert!(matches!(error, CufinderError::ApiError { status: 404, .. }));
+    }
+
+    let stats = sdk.client().cache().unwrap().stats();
+    assert_eq!((stats.hits, stats.negative_hits, stats.misses), (2, 1, 2));
+    assert_eq!(stats.entries, 2);
+    assert_eq!(stats.hit_rate(), 0.5);
+    dtc.assert_async().await;
+    missing.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_cache_skips_paginated_search_and_expires() {
+    let mut server = Server::new_async().await;
+    let search = server
+        .mock("POST", "/cse")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "companies": [] }).to_string())
+        .expect(2)
+        .create_async()
+        .await;
+    let dtc = server
+        .mock("POST", "/dtc")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "company_name": "TechCorp" }).to_string())
+        .expect(2)
+        .create_async()
+        .await;
+
+    let config = CacheConfig::new().endpoint_ttl("/dtc", Duration::from_millis(50));
+    let sdk = create_test_sdk(&server.url(), 0).with_cache(config);
+
+    sdk.cse(Default::default()).await.unwrap();
+    sdk.cse(Default::default()).await.unwrap();
+
+    sdk.dtc("techcorp.com").await.unwrap();
+    tokio::time::sleep(Duration::from_millis(80)).await;
+    sdk.dtc("techcorp.com").await.unwrap();
+
+    assert_eq!(sdk.client().cache().unwrap().stats().expirations, 1);
+    search.assert_async().await;
+    dtc.assert_async().await;
+}
+
+#[test]
+fn test_cache_key_and_lru_eviction() {
+    assert_eq!(
+        ResponseCache::key("/tep", "full_name=Jane&company=Acme&extra="),
+        ResponseCache::key("/tep", "company=Acme&full_name=Jane"),
+    );
+    assert_ne!(
+        ResponseCache::key("/tep", "company=Acme"),
+        ResponseCache::key("/enc", "company=Acme"),
+    );
+
+    let cache = ResponseCache::new(CacheConfig::new().max_entries(2));
+    let found = |n: i32| CachedResponse::Found(json!(n));
+    cache.insert("/enc", "a".into(), found(1)).unwrap();
+    cache.insert("/enc", "b".into(), found(2)).unwrap();
+    assert_eq!(cache.get("a"), Some(found(1)));
+    cache.insert("/enc", "c".into(), found(3)).unwrap();
+
+    assert_eq!(cache.get("b"), None);
+    assert_eq!(cache.get("a"), Some(found(1)));
+    assert_eq!(cache.get("c"), Some(found(3)));
+    assert_eq!(cache.stats().evictions, 1);
+
+    let no_negative = ResponseCache::new(CacheConfig::new().negative_ttl(None));
+    no_negative
+        .insert("/enc", "a".into(), CachedResponse::NotFound("gone".into()))
+        .unwrap();
+    assert_eq!(no_negative.stats().entries, 0);
+}
+
+#[tokio::test]
+async fn test_offline_mode_serves_imported_cache() {
+    let mut server = Server::new_async().await;
+    let dtc = server
+        .mock("POST", "/dtc")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "company_name": "TechCorp", "credit_count": 1 }).to_string())
+        .expect(1)
+        .create_async()
+        .await;
+    let path = std::env::temp_dir().join(format!("cufinder-export-{}.jsonl", std::process::id()));
+
+    let config = CacheConfig::new().ttl(Duration::from_millis(1));
+    let online = create_test_sdk(&server.url(), 0).with_cache(config);
+    online.dtc("techcorp.com").await.unwrap();
+    let exported = online
+        .client()
+        .cache()
+        .unwrap()
+        .export_jsonl(&path)
+        .unwrap();
+    assert_eq!(exported, 1);
+    tokio::time::sleep(Duration::from_millis(5)).await;
+
+    let offline = create_test_sdk(&server.url(), 0).with_cache(CacheConfig::new().offline(true));
+    let cache = offline.client().cache().unwrap();
+    assert_eq!(cache.import_jsonl(&path).unwrap(), 1);
+
+    // Stale entries are still served, and nothing reaches the network
+    let cached = offline.dtc("techcorp.com").await.unwrap();
+    assert_eq!(cached.company_name, "TechCorp");
+
+    let error = offline.dtc("other.com").await.unwrap_err();
+    assert!(
+        matches!(error, CufinderError::CacheMiss(ref key) if key == "/dtc?company_website=other.com")
+    );
+    assert_eq!(error.kind(), ErrorKind::CacheMiss);
+    assert!(!error.is_retryable());
+
+    let error = offline.cse(Default::default()).await.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::CacheMiss);
+
+    dtc.assert_async().await;
+    let _ = std::fs::remove_file(&path);
+}
+
+fn store_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("cufinder-cache-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+fn entry(key: &str, body: serde_json::Value, fetched_at: u64) -> CacheEntry {
+    CacheEntry {
+        key: key.to_string(),
+        endpoint: key.split('?').next().unwrap().to_string(),
+        response: CachedResponse::Found(body),
+        fetched_at,
+        expires_at: u64::MAX,
+    }
+}
+
+#[tokio::test]
+async fn test_file_store_survives_restart_and_invalidates() {
+    let mut server = Server::new_async().await;
+    let dtc = server
+        .mock("POST", "/dtc")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "company_name": "TechCorp", "credit_count": 1 }).to_string())
+        .expect(2)
+        .create_async()
+        .await;
+    let dir = store_dir("restart");
+
+    let sdk = create_test_sdk(&server.url(), 0)
+        .with_cache_store(CacheConfig::new(), FileStore::open(&dir).unwrap());
+    sdk.dtc("techcorp.com").await.unwrap();
+    drop(sdk);
+
+    let sdk = create_test_sdk(&server.url(), 0)
+        .with_cache_store(CacheConfig::new(), FileStore::open(&dir).unwrap());
+    let cached = sdk.dtc("techcorp.com").await.unwrap();
+    assert_eq!(cached.company_name, "TechCorp");
+    assert_eq!(sdk.client().cache().unwrap().stats().hits, 1);
+
+    let params = DtcParams {
+        company_website: "techcorp.com".to_string(),
+    };
+    let cache = sdk.client().cache().unwrap();
+    assert!(cache.invalidate_request("/dtc", &params).unwrap());
+    sdk.dtc("techcorp.com").await.unwrap();
+
+    dtc.assert_async().await;
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_file_store_limits_and_purge_by_subject() {
+    let dir = store_dir("purge");
+    let store = FileStore::open(&dir).unwrap().max_entries(3);
+    assert!(store.is_blocking());
+    assert!(!MemoryStore::new(10).is_blocking());
+
+    store
+        .put(entry(
+            "/rel?email=jane%40acme.com",
+            json!({ "person": {} }),
+            1,
+        ))
+        .unwrap();
+    store
+        .put(entry(
+            "/enc?query=acme.com",
+            json!({ "ceo": "JANE@acme.com" }),
+            2,
+        ))
+        .unwrap();
+    store
+        .put(entry(
+            "/enc?query=other.com",
+            json!({ "ceo": "bob@other.com" }),
+            3,
+        ))
+        .unwrap();
+    let evicted = store
+        .put(entry("/dtc?company_website=x.com", json!({}), 4))
+        .unwrap();
+    assert_eq!(evicted, 1);
+    assert!(store.get("/rel?email=jane%40acme.com").unwrap().is_none());
+    assert!(store.remove("/dtc?company_website=x.com").unwrap());
+
+    store
+        .put(entry(
+            "/fwe?linkedin_url=linkedin.com%2Fin%2Fjane",
+            json!({}),
+            5,
+        ))
+        .unwrap();
+    assert_eq!(store.purge_subject("jane@acme.com").unwrap(), 1);
+    assert_eq!(store.purge_subject("linkedin.com/in/jane/").unwrap(), 1);
+
+    let reopened = FileStore::open(&dir).unwrap();
+    let mut keys: Vec<_> = reopened
+        .entries()
+        .unwrap()
+        .into_iter()
+        .map(|e| e.key)
+        .collect();
+    keys.sort();
+    assert_eq!(keys, vec!["/enc?query=other.com"]);
+    assert!(reopened.size_bytes() > 0);
+
+    reopened.clear().unwrap();
+    assert!(FileStore::open(&dir).unwrap().is_empty());
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,144 @@
+mod common;
+
+use common::create_test_sdk;
+use cufinder_rust::{CacheConfig, ClientConfig, CufinderError, CufinderSDK, ErrorKind};
+use futures::future::join_all;
+use mockito::Server;
+use serde_json::json;
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+#[tokio::test]
+async fn test_identical_concurrent_requests_share_one_call() {
+    let mut server = Server::new_async().await;
+    let dtc = server
+        .mock("POST", "/dtc")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "company_name": "TechCorp", "credit_count": 1 }).to_string())
+        .expect(2)
+        .create_async()
+        .await;
+
+    let sdk = create_test_sdk(&server.url(), 0);
+    let results = join_all((0..5).map(|_| sdk.dtc("techcorp.com"))).await;
+    assert!(
+        results
+            .iter()
+            .all(|r| r.as_ref().unwrap().company_name == "TechCorp")
+    );
+    assert_eq!(sdk.client().saved_calls(), 4);
+
+    // Once the shared call has finished, the next request is sent again
+    sdk.dtc("techcorp.com").await.unwrap();
+    assert_eq!(sdk.client().saved_calls(), 4);
+    dtc.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_shared_errors_and_disabled_coalescing() {
+    let mut server = Server::new_async().await;
+    let missing = server
+        .mock("POST", "/enc")
+        .with_status(404)
+        .with_body("company not found")
+        .expect(1)
+        .create_async()
+        .await;
+    let dtc = server
+        .mock("POST", "/dtc")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "company_name": "TechCorp" }).to_string())
+        .expect(3)
+        .create_async()
+        .await;
+
+    let sdk = create_test_sdk(&server.url(), 0);
+    let results = join_all((0..3).map(|_| sdk.enc("missing.com"))).await;
+    for result in results {
+        assert!(matches!(
+            result,
+            Err(CufinderError::ApiError { status: 404, ref message }) if message == "company not found"
+        ));
+    }
+
+    let sdk = create_test_sdk(&server.url(), 0).with_coalescing(false);
+    join_all((0..3).map(|_| sdk.dtc("techcorp.com"))).await;
+    assert_eq!(sdk.client().saved_calls(), 0);
+
+    missing.assert_async().await;
+    dtc.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_coalescing_across_spawned_tasks_fills_cache_once() {
+    let mut server = Server::new_async().await;
+    let dtc = server
+        .mock("POST", "/dtc")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "company_name": "TechCorp" }).to_string())
+        .expect(1)
+        .create_async()
+        .await;
+
+    let sdk = Arc::new(create_test_sdk(&server.url(), 0).with_cache(CacheConfig::new()));
+    let tasks: Vec<_> = (0..4)
+        .map(|_| {
+            let sdk = sdk.clone();
+            tokio::spawn(async move { sdk.dtc("techcorp.com").await })
+        })
+        .collect();
+    for task in join_all(tasks).await {
+        assert_eq!(task.unwrap().unwrap().company_name, "TechCorp");
+    }
+
+    let stats = sdk.client().cache().unwrap().stats();
+    assert_eq!(stats.entries, 1);
+    assert_eq!(
+        stats.hits + sdk.client().saved_calls(),
+        3,
+        "every call but one is served by the cache or a shared request"
+    );
+    dtc.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_shared_timeouts_keep_their_classification() {
+    // A server that accepts connections and never answers
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let connections = Arc::new(AtomicUsize::new(0));
+    let counter = connections.clone();
+    tokio::spawn(async move {
+        let mut open = Vec::new();
+        while let Ok((socket, _)) = listener.accept().await {
+            counter.fetch_add(1, Ordering::SeqCst);
+            open.push(socket);
+        }
+    });
+
+    let sdk = CufinderSDK::with_config(ClientConfig {
+        api_key: "test-api-key".to_string(),
+        base_url: url,
+        timeout: Duration::from_millis(100),
+        max_retries: 0,
+    })
+    .unwrap();
+    let results = join_all((0..3).map(|_| sdk.dtc("techcorp.com"))).await;
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+    assert_eq!(sdk.client().saved_calls(), 2);
+
+    // The caller that sent the request and the ones that shared it see the same failure
+    for result in results {
+        let error = result.unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::Http);
+        assert!(error.is_timeout() && !error.is_retryable());
+    }
+}
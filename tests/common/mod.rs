@@ -0,0 +1,16 @@
+//! Helpers shared by the integration tests
+
+use cufinder_rust::{ClientConfig, CufinderSDK};
+use std::time::Duration;
+
+/// An SDK talking to `base_url`, usually a mock server, that retries failed requests up to
+/// `max_retries` times
+pub fn create_test_sdk(base_url: &str, max_retries: u32) -> CufinderSDK {
+    CufinderSDK::with_config(ClientConfig {
+        api_key: "test-api-key".to_string(),
+        base_url: base_url.to_string(),
+        timeout: Duration::from_secs(5),
+        max_retries,
+    })
+    .unwrap()
+}
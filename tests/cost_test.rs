@@ -0,0 +1,154 @@
+mod common;
+
+use common::create_test_sdk;
+use cufinder_rust::{
+    CufinderError, DtcParams, ErrorKind, PaginationOptions,
+    cost::{CostEstimate, CostTable},
+    ledger::{LedgerEntry, LedgerReport},
+};
+use mockito::Server;
+
+#[tokio::test]
+async fn test_dry_run_returns_encoded_request() {
+    let mut server = Server::new_async().await;
+    let tep = server.mock("POST", "/tep").expect(0).create_async().await;
+
+    let sdk = create_test_sdk(&server.url(), 0).with_dry_run(true);
+    let error = sdk.tep("Jane Doe", "Acme & Co").await.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::DryRun);
+
+    let CufinderError::DryRun(request) = error else {
+        panic!("expected a dry run");
+    };
+    assert_eq!(request.method, "POST");
+    assert_eq!(request.url, format!("{}/tep", server.url()));
+    assert_eq!(request.body, "full_name=Jane+Doe&company=Acme+%26+Co");
+    assert!(
+        request
+            .headers
+            .contains(&("x-api-key".to_string(), "****-key".to_string()))
+    );
+    assert!(
+        request
+            .headers
+            .iter()
+            .any(|(name, value)| name == "Content-Type" && value.contains("urlencoded"))
+    );
+    assert!(!request.to_string().contains("test-api-key"));
+
+    let params = DtcParams {
+        company_website: "acme.com".to_string(),
+    };
+    let prepared = sdk.client().prepare("/dtc", &params).unwrap();
+    assert_eq!(prepared.body, "company_website=acme.com");
+    tep.assert_async().await;
+}
+
+#[test]
+fn test_cost_table_estimates_batches_and_searches() {
+    let table = CostTable::new().default_cost(1).endpoint("/enc", 3);
+    assert_eq!(
+        table.estimate("/enc", 1000),
+        CostEstimate {
+            requests: 1000,
+            credits: 3000
+        }
+    );
+    assert_eq!(table.estimate("/dtc", 10).credits, 10);
+
+    let search = CostTable::new().endpoint("/cse", 5);
+    let options = PaginationOptions::new();
+    assert_eq!(search.estimate_pages("/cse", &options, 25, None), None);
+    assert_eq!(
+        search.estimate_pages("/cse", &options, 25, Some(101)),
+        Some(CostEstimate {
+            requests: 5,
+            credits: 25
+        })
+    );
+
+    let capped = PaginationOptions::new().start_page(3).max_items(30);
+    assert_eq!(
+        search
+            .estimate_pages("/cse", &capped, 25, Some(101))
+            .unwrap()
+            .requests,
+        2
+    );
+    let paged = PaginationOptions::new().max_pages(2);
+    assert_eq!(
+        search
+            .estimate_pages("/cse", &paged, 25, None)
+            .unwrap()
+            .credits,
+        10
+    );
+
+    let total: CostEstimate = [table.estimate("/enc", 2), table.estimate("/dtc", 3)]
+        .into_iter()
+        .sum();
+    assert_eq!(
+        total,
+        CostEstimate {
+            requests: 5,
+            credits: 9
+        }
+    );
+}
+
+#[test]
+fn test_cost_table_from_ledger() {
+    let entry = |endpoint: &str, credits: i64, cache_hit: bool| LedgerEntry {
+        timestamp: 0,
+        endpoint: endpoint.to_string(),
+        credits,
+        confidence_level: None,
+        cache_hit,
+        shared: false,
+        tag: None,
+        error: None,
+    };
+    let entries = vec![
+        entry("/enc", 3, false),
+        entry("/enc", 4, false),
+        entry("/enc", 0, true),
+        entry("/dtc", 1, false),
+    ];
+
+    let table = CostTable::from_report(&LedgerReport::from_entries(&entries));
+    assert_eq!(table.cost("/enc"), 4);
+    assert_eq!(table.cost("/dtc"), 1);
+    assert_eq!(table.cost("/pse"), table.default_cost);
+}
+
+#[test]
+fn test_cost_table_ignores_cached_errors_and_shared_calls() {
+    let entry =
+        |credits: i64, cache_hit: bool, shared: bool, error: Option<ErrorKind>| LedgerEntry {
+            timestamp: 0,
+            endpoint: "/enc".to_string(),
+            credits,
+            confidence_level: None,
+            cache_hit,
+            shared,
+            tag: None,
+            error,
+        };
+    let entries = vec![
+        // A cached 404 and an offline cache miss are both cache hits and errors
+        entry(0, true, false, Some(ErrorKind::Api)),
+        entry(0, true, false, Some(ErrorKind::CacheMiss)),
+        entry(0, false, true, None),
+        entry(0, false, true, None),
+        entry(0, false, false, Some(ErrorKind::Http)),
+        entry(5, false, false, None),
+    ];
+
+    let report = LedgerReport::from_entries(&entries);
+    assert_eq!(report.by_endpoint["/enc"].charged, 1);
+    let table = CostTable::from_report(&report);
+    assert_eq!(table.cost("/enc"), 5);
+
+    let unpaid = CostTable::from_report(&LedgerReport::from_entries(&entries[..2]));
+    assert_eq!(unpaid.cost("/enc"), unpaid.default_cost);
+}
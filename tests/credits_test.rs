@@ -0,0 +1,93 @@
+mod common;
+
+use common::create_test_sdk;
+use cufinder_rust::{CreditBudget, CufinderError, ErrorKind};
+use mockito::Server;
+use serde_json::json;
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+async fn mock_endpoint(
+    server: &mut mockito::ServerGuard,
+    path: &str,
+    credits: i32,
+    hits: usize,
+) -> mockito::Mock {
+    server
+        .mock("POST", path)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "company_name": "TechCorp", "credit_count": credits }).to_string())
+        .expect(hits)
+        .create_async()
+        .await
+}
+
+#[tokio::test]
+async fn test_budget_soft_and_hard_limits() {
+    let mut server = Server::new_async().await;
+    let dtc = mock_endpoint(&mut server, "/dtc", 2, 3).await;
+    let lcuf = mock_endpoint(&mut server, "/lcuf", 1, 0).await;
+
+    let soft_calls = Arc::new(AtomicUsize::new(0));
+    let counter = soft_calls.clone();
+    let budget = CreditBudget::new()
+        .soft_limit(3)
+        .hard_limit(6)
+        .on_soft_limit(move |usage| {
+            assert!(usage.total >= 3);
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+    let sdk = create_test_sdk(&server.url(), 0).with_budget(budget);
+
+    for domain in ["a.com", "b.com", "c.com"] {
+        sdk.dtc(domain).await.unwrap();
+    }
+    assert_eq!(soft_calls.load(Ordering::SeqCst), 1);
+    assert_eq!(sdk.credits().remaining(), Some(0));
+
+    let error = sdk.lcuf("TechCorp").await.unwrap_err();
+    assert!(matches!(
+        error,
+        CufinderError::BudgetExceeded { spent: 6, limit: 6 }
+    ));
+    assert_eq!(error.kind(), ErrorKind::Budget);
+    assert!(!error.is_retryable());
+
+    let usage = sdk.credits().usage();
+    assert_eq!(usage.total, 6);
+    assert_eq!(usage.requests, 3);
+    assert_eq!(usage.by_endpoint.get("/dtc"), Some(&6));
+    dtc.assert_async().await;
+    lcuf.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_scoped_budget_charges_parent() {
+    let mut server = Server::new_async().await;
+    let dtc = mock_endpoint(&mut server, "/dtc", 2, 3).await;
+
+    let sdk = create_test_sdk(&server.url(), 0).with_budget(CreditBudget::new().hard_limit(100));
+    let job = sdk.scoped(CreditBudget::new().hard_limit(4));
+
+    job.dtc("a.com").await.unwrap();
+    job.dtc("b.com").await.unwrap();
+    let error = job.dtc("c.com").await.unwrap_err();
+    assert!(matches!(
+        error,
+        CufinderError::BudgetExceeded { limit: 4, .. }
+    ));
+
+    // The enclosing SDK is charged for the scope but still has budget left
+    sdk.dtc("c.com").await.unwrap();
+    assert_eq!(job.credits().spent(), 4);
+    assert_eq!(sdk.credits().spent(), 6);
+    assert_eq!(sdk.credits().remaining(), Some(94));
+    assert_eq!(job.credits().remaining(), Some(0));
+
+    job.credits().reset();
+    assert_eq!(job.credits().remaining(), Some(4));
+    dtc.assert_async().await;
+}
@@ -0,0 +1,114 @@
+#![cfg(feature = "time")]
+
+use cufinder_rust::{
+    CaaResponse, CjaResponse, EncCompany,
+    dates::{parse_date, parse_datetime, parse_relative},
+};
+use serde_json::json;
+use time::{Duration, OffsetDateTime, macros::{date, datetime}};
+
+#[test]
+fn test_parse_datetime_formats() {
+    assert_eq!(parse_datetime("2024-03-05T10:30:00Z"), Some(datetime!(2024-03-05 10:30:00 UTC)));
+    assert_eq!(parse_datetime("2024-03-05T10:30:00+02:00"), Some(datetime!(2024-03-05 08:30:00 UTC)));
+    assert_eq!(parse_datetime("2024-03-05 10:30:00"), Some(datetime!(2024-03-05 10:30:00 UTC)));
+    assert_eq!(parse_datetime("2024-03-05"), Some(datetime!(2024-03-05 00:00:00 UTC)));
+    assert_eq!(parse_datetime("1709634600"), Some(datetime!(2024-03-05 10:30:00 UTC)));
+    assert_eq!(parse_datetime("1709634600000"), Some(datetime!(2024-03-05 10:30:00 UTC)));
+    assert_eq!(parse_datetime("3 days ago"), None);
+    assert_eq!(parse_datetime(""), None);
+
+    assert_eq!(parse_date("2015"), Some(date!(2015-01-01)));
+    assert_eq!(parse_date("2015-06"), Some(date!(2015-06-01)));
+    assert_eq!(parse_date("2015-13-01"), None);
+}
+
+#[test]
+fn test_parse_relative_text() {
+    let reference = datetime!(2024-03-10 12:00:00 UTC);
+
+    assert_eq!(parse_relative("3 days ago", reference), Some(reference - Duration::days(3)));
+    assert_eq!(parse_relative("Posted 30+ days ago", reference), Some(reference - Duration::days(30)));
+    assert_eq!(parse_relative("an hour ago", reference), Some(reference - Duration::HOUR));
+    assert_eq!(parse_relative("2w", reference), Some(reference - Duration::weeks(2)));
+    assert_eq!(parse_relative("1mo •", reference), Some(reference - Duration::days(30)));
+    assert_eq!(parse_relative("1 year ago", reference), Some(reference - Duration::days(365)));
+    assert_eq!(parse_relative("Just now", reference), Some(reference));
+    assert_eq!(parse_relative("yesterday", reference), Some(reference - Duration::DAY));
+    assert_eq!(parse_relative("sometime", reference), None);
+}
+
+#[test]
+fn test_caa_sort_and_filter() {
+    let mut response: CaaResponse = serde_json::from_value(json!({
+        "activities": [
+            { "activity_id": "old", "activity_posted_at": "2020-01-01T00:00:00Z" },
+            { "activity_id": "undated" },
+            { "activity_id": "recent", "activity_posted_at": "2 days ago" },
+            { "activity_id": "mid", "activity_posted_at": "2023-06-15" }
+        ]
+    }))
+    .unwrap();
+
+    assert_eq!(response.activities[0].posted_at(), Some(datetime!(2020-01-01 00:00:00 UTC)));
+    assert_eq!(response.activities[2].posted_at(), None);
+
+    // "2 days ago" resolves to 2023-06-14, just before "mid"
+    let reference = datetime!(2023-06-16 00:00:00 UTC);
+    response.sort_by_posted_at_relative_to(reference);
+    let ids: Vec<_> = response.activities.iter().map(|a| a.activity_id.as_deref().unwrap()).collect();
+    assert_eq!(ids, vec!["mid", "recent", "old", "undated"]);
+
+    let recent = response.posted_since_relative_to(reference - Duration::days(1), reference);
+    assert_eq!(recent.len(), 1);
+    assert_eq!(recent[0].activity_id.as_deref(), Some("mid"));
+
+    let between = response.posted_between_relative_to(
+        datetime!(2019-01-01 00:00:00 UTC),
+        datetime!(2023-06-14 12:00:00 UTC),
+        reference,
+    );
+    assert_eq!(between.len(), 2);
+
+    // Without a reference, relative text resolves against the current time
+    response.sort_by_posted_at();
+    let ids: Vec<_> = response.activities.iter().map(|a| a.activity_id.as_deref().unwrap()).collect();
+    assert_eq!(ids, vec!["recent", "mid", "old", "undated"]);
+    assert_eq!(response.posted_since(OffsetDateTime::now_utc() - Duration::weeks(1)).len(), 1);
+}
+
+#[test]
+fn test_cja_dates() {
+    let mut response: CjaResponse = serde_json::from_value(json!({
+        "jobs": [
+            {
+                "company": { "name": "TechCorp", "founded_date": "2010" },
+                "job": { "job_id": "1", "posted_at_text": "3 weeks ago" }
+            },
+            {
+                "company": { "name": "TechCorp", "founded_date": "2010-04-12" },
+                "job": { "job_id": "2", "posted_at": "2099-01-01T00:00:00Z", "posted_at_text": "1 day ago" }
+            }
+        ]
+    }))
+    .unwrap();
+
+    assert_eq!(response.jobs[0].company.founded(), Some(date!(2010-01-01)));
+    assert_eq!(response.jobs[1].company.founded(), Some(date!(2010-04-12)));
+
+    let reference = datetime!(2024-03-10 12:00:00 UTC);
+    assert_eq!(response.jobs[0].job.posted_at(), None);
+    assert_eq!(response.jobs[0].job.posted_at_relative_to(reference), Some(reference - Duration::weeks(3)));
+    assert_eq!(response.jobs[1].job.posted_at_relative_to(reference), Some(datetime!(2099-01-01 00:00:00 UTC)));
+
+    response.sort_by_posted_at_relative_to(reference);
+    assert_eq!(response.jobs[0].job.job_id.as_deref(), Some("2"));
+    assert_eq!(response.posted_since_relative_to(reference - Duration::weeks(4), reference).len(), 2);
+    assert_eq!(response.posted_since_relative_to(reference - Duration::weeks(2), reference).len(), 1);
+}
+
+#[test]
+fn test_founded_year() {
+    let company: EncCompany = serde_json::from_value(json!({ "name": "TechCorp", "founded_year": "1998" })).unwrap();
+    assert_eq!(company.founded(), Some(date!(1998-01-01)));
+}
@@ -0,0 +1,167 @@
+mod common;
+
+use common::create_test_sdk;
+use cufinder_rust::{
+    CjaParams, CseParams, CufinderError, DtcParams, Endpoint, LbsParams, PseParams, Result,
+    TepParams,
+    endpoint::{Cja, Cse, Dtc, Enc, Lbs, Pse, Tep},
+};
+use mockito::{Matcher, Server};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// An endpoint the SDK does not know about yet, defined by the caller
+struct Echo;
+
+#[derive(Serialize)]
+struct EchoParams {
+    text: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct EchoResponse {
+    text: String,
+    credit_count: i32,
+}
+
+impl Endpoint for Echo {
+    type Params = EchoParams;
+    type Response = EchoResponse;
+
+    const PATH: &'static str = "/echo";
+
+    fn validate(params: &EchoParams) -> Result<()> {
+        if params.text.len() > 10 {
+            return Err(CufinderError::ValidationError(
+                "text is too long".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_endpoint_paths_and_validation() {
+    assert_eq!(Dtc::PATH, "/dtc");
+    assert_eq!(Enc::PATH, "/enc");
+
+    let missing = Tep::validate(&TepParams {
+        full_name: "Jane Doe".to_string(),
+        company: String::new(),
+    })
+    .unwrap_err();
+    assert_eq!(missing.to_string(), "Validation error: company is required");
+}
+
+#[tokio::test]
+async fn test_call_builtin_endpoint() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/dtc")
+        .match_body(Matcher::UrlEncoded(
+            "company_website".into(),
+            "stripe.com".into(),
+        ))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "company_name": "Stripe", "credit_count": 1 }).to_string())
+        .create_async()
+        .await;
+
+    let sdk = create_test_sdk(&server.url(), 0);
+    let response = sdk
+        .call::<Dtc>(DtcParams {
+            company_website: "stripe.com".to_string(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(response.company_name, "Stripe");
+    mock.assert_async().await;
+
+    // Validation runs before anything is sent
+    let error = sdk
+        .call::<Dtc>(DtcParams {
+            company_website: String::new(),
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(error, CufinderError::ValidationError(_)));
+}
+
+#[tokio::test]
+async fn test_call_checks_search_params() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", Matcher::Any)
+        .expect(0)
+        .create_async()
+        .await;
+
+    let sdk = create_test_sdk(&server.url(), 0);
+    let inverted = CseParams {
+        followers_count_min: Some(500),
+        followers_count_max: Some(10),
+        ..Default::default()
+    };
+    let errors = [
+        sdk.call::<Cse>(inverted).await.unwrap_err(),
+        sdk.call::<Pse>(PseParams {
+            page: Some(0),
+            ..Default::default()
+        })
+        .await
+        .unwrap_err(),
+        sdk.call::<Lbs>(LbsParams {
+            name: Some(String::new()),
+            ..Default::default()
+        })
+        .await
+        .unwrap_err(),
+        sdk.call::<Cja>(CjaParams {
+            page: Some(-1),
+            ..Default::default()
+        })
+        .await
+        .unwrap_err(),
+    ];
+    assert!(
+        errors
+            .iter()
+            .all(|error| matches!(error, CufinderError::ValidationError(_)))
+    );
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_call_custom_endpoint() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/echo")
+        .match_body(Matcher::UrlEncoded("text".into(), "hello".into()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "text": "hello", "credit_count": 2 }).to_string())
+        .expect(1)
+        .create_async()
+        .await;
+
+    let sdk = create_test_sdk(&server.url(), 0);
+    let response = sdk
+        .call::<Echo>(EchoParams {
+            text: "hello".to_string(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(response.text, "hello");
+    assert_eq!(response.credit_count, 2);
+    assert_eq!(sdk.credits().spent(), 2);
+
+    let error = sdk
+        .call::<Echo>(EchoParams {
+            text: "far too long to send".to_string(),
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(error, CufinderError::ValidationError(_)));
+    mock.assert_async().await;
+}
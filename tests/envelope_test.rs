@@ -0,0 +1,129 @@
+mod common;
+
+use common::create_test_sdk;
+use cufinder_rust::{ApiEnvelope, ApiResponse, CacheConfig, ErrorKind, PseParams};
+use mockito::Server;
+use serde_json::json;
+
+fn pse_body() -> String {
+    json!({
+        "status": 1,
+        "data": {
+            "confidence_level": 80,
+            "credit_count": 2,
+            "peoples": [{ "full_name": "Jane Doe" }, { "full_name": "John Roe" }]
+        },
+        "meta_data": { "total": 2 }
+    })
+    .to_string()
+}
+
+#[test]
+fn test_from_body_with_and_without_envelope() {
+    let envelope = ApiEnvelope::<Vec<String>>::from_body(
+        r#"{"status": 1, "data": ["a", "b"], "meta_data": {"page": 2}}"#,
+    )
+    .unwrap();
+    assert_eq!(envelope.status, Some(json!(1)));
+    assert_eq!(envelope.data, vec!["a", "b"]);
+    assert_eq!(envelope.meta_data, Some(json!({ "page": 2 })));
+
+    let bare = ApiEnvelope::<serde_json::Value>::from_body(r#"{"company_name": "Acme"}"#).unwrap();
+    assert_eq!(bare.data, json!({ "company_name": "Acme" }));
+    assert_eq!(bare.meta_data, None);
+
+    // Arrays keep their shape when merged, since there is nowhere to put meta_data
+    let array = ApiEnvelope::<serde_json::Value>::from_body(r#"{"data": [1], "meta_data": {}}"#)
+        .unwrap()
+        .into_merged();
+    assert_eq!(array, json!([1]));
+
+    assert!(ApiEnvelope::<Vec<String>>::from_body(r#"{"data": [1]}"#).is_err());
+}
+
+#[tokio::test]
+async fn test_typed_endpoint_keeps_meta_data_and_credits() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/pse")
+        .with_status(200)
+        .with_body(pse_body())
+        .expect(1)
+        .create_async()
+        .await;
+
+    let sdk =
+        create_test_sdk(&server.url(), 0).with_cache(CacheConfig::default().cache_paginated(true));
+    let fresh = sdk.pse(PseParams::default()).await.unwrap();
+    assert_eq!(fresh.peoples.len(), 2);
+    assert_eq!(fresh.base.meta_data, Some(json!({ "total": 2 })));
+    assert_eq!(fresh.credit_count(), 2);
+    assert_eq!(fresh.base.confidence_level, Some(80));
+    assert_eq!(sdk.credits().spent(), 2);
+
+    // The cached copy is stored unwrapped and decodes to the same response, uncharged
+    let cached = sdk.pse(PseParams::default()).await.unwrap();
+    assert_eq!(cached.peoples.len(), 2);
+    assert_eq!(cached.base.meta_data, Some(json!({ "total": 2 })));
+    assert_eq!(cached.credit_count(), 0);
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_credits_are_read_while_decoding() {
+    let mut server = Server::new_async().await;
+    server
+        .mock("POST", "/dtc")
+        .with_status(200)
+        .with_body(
+            json!({
+                "data": {
+                    "company_name": "TechCorp",
+                    "parent": { "credit_count": 99 },
+                    "credit_count": 3,
+                    "confidence_level": 70
+                }
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+    server
+        .mock("POST", "/enc")
+        .with_status(200)
+        .with_body(json!({ "data": { "credit_count": 4, "company": "unexpected" } }).to_string())
+        .create_async()
+        .await;
+
+    let sdk = create_test_sdk(&server.url(), 0);
+    let response = sdk.dtc("techcorp.com").await.unwrap();
+    assert_eq!(response.company_name, "TechCorp");
+    assert_eq!(response.credit_count(), 3);
+    assert_eq!(response.extra["parent"], json!({ "credit_count": 99 }));
+    assert_eq!(sdk.credits().spent(), 3);
+
+    // A response that does not decode was still answered, and charged, by the API
+    let error = sdk.enc("techcorp.com").await.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Json);
+    assert_eq!(sdk.credits().spent(), 7);
+}
+
+#[tokio::test]
+async fn test_array_data() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/tags")
+        .with_status(200)
+        .with_body(json!({ "data": ["saas", "b2b"], "meta_data": { "total": 2 } }).to_string())
+        .expect(2)
+        .create_async()
+        .await;
+
+    let sdk = create_test_sdk(&server.url(), 0);
+    let tags: Vec<String> = sdk.call_json("/tags", [("q", "acme")]).await.unwrap();
+    assert_eq!(tags, vec!["saas", "b2b"]);
+
+    let raw = sdk.call_raw("/tags", [("q", "acme")]).await.unwrap();
+    assert_eq!(raw.data, json!(["saas", "b2b"]));
+    mock.assert_async().await;
+}
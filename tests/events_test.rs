@@ -0,0 +1,202 @@
+mod common;
+
+use common::create_test_sdk;
+use cufinder_rust::{CufinderSDK, ErrorKind, Event, PaginationOptions, batch::BatchOptions};
+use futures::TryStreamExt;
+use mockito::{Matcher, Server};
+use serde_json::json;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+fn recording_sdk(base_url: &str, max_retries: u32) -> (CufinderSDK, Arc<Mutex<Vec<Event>>>) {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    let sdk = create_test_sdk(base_url, max_retries)
+        .with_observer(move |event: &Event| sink.lock().unwrap().push(event.clone()));
+    (sdk, events)
+}
+
+#[tokio::test]
+async fn test_request_retry_and_credit_events() {
+    let mut server = Server::new_async().await;
+    let _unavailable = server
+        .mock("POST", "/dtc")
+        .with_status(503)
+        .with_header("retry-after", "0")
+        .with_body("unavailable")
+        .expect(1)
+        .create_async()
+        .await;
+    let _success = server
+        .mock("POST", "/dtc")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "company_name": "TechCorp", "credit_count": 2 }).to_string())
+        .create_async()
+        .await;
+
+    let (sdk, events) = recording_sdk(&server.url(), 1);
+    sdk.dtc("techcorp.com").await.unwrap();
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 6);
+    assert_eq!(
+        events[0],
+        Event::RequestStarted {
+            endpoint: "/dtc".into(),
+            attempt: 0
+        }
+    );
+    assert!(matches!(
+        events[1],
+        Event::RequestFinished {
+            attempt: 0,
+            error: Some(ErrorKind::Api),
+            ..
+        }
+    ));
+    assert_eq!(
+        events[2],
+        Event::RetryScheduled {
+            endpoint: "/dtc".into(),
+            attempt: 0,
+            delay: Duration::ZERO,
+            error: ErrorKind::Api
+        }
+    );
+    assert!(matches!(
+        events[3],
+        Event::RequestStarted { attempt: 1, .. }
+    ));
+    assert!(matches!(
+        events[4],
+        Event::RequestFinished {
+            attempt: 1,
+            error: None,
+            ..
+        }
+    ));
+    assert_eq!(
+        events[5],
+        Event::CreditsConsumed {
+            endpoint: "/dtc".into(),
+            credits: 2
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_page_and_batch_item_events() {
+    let mut server = Server::new_async().await;
+    for (page, names) in [(1, vec!["A", "B"]), (2, vec!["C"])] {
+        let companies: Vec<_> = names.iter().map(|name| json!({ "name": name })).collect();
+        server
+            .mock("POST", "/cse")
+            .match_body(Matcher::UrlEncoded("page".into(), page.to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({ "companies": companies, "meta_data": { "total": 3 } }).to_string())
+            .create_async()
+            .await;
+    }
+    server
+        .mock("POST", "/dtc")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "company_name": "TechCorp", "credit_count": 1 }).to_string())
+        .create_async()
+        .await;
+
+    let (sdk, events) = recording_sdk(&server.url(), 0);
+    let companies: Vec<_> = sdk
+        .cse_stream(Default::default(), PaginationOptions::new())
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(companies.len(), 3);
+
+    let pages: Vec<_> = events
+        .lock()
+        .unwrap()
+        .drain(..)
+        .filter(|event| matches!(event, Event::PageFetched { .. }))
+        .collect();
+    assert_eq!(
+        pages,
+        vec![
+            Event::PageFetched {
+                page: 1,
+                items: 2,
+                total: Some(3)
+            },
+            Event::PageFetched {
+                page: 2,
+                items: 1,
+                total: Some(3)
+            },
+        ]
+    );
+
+    sdk.batch(
+        vec!["a.com", ""],
+        BatchOptions::new(),
+        |sdk, domain| async move { sdk.dtc(domain).await },
+    )
+    .await;
+    let items: Vec<_> = events
+        .lock()
+        .unwrap()
+        .drain(..)
+        .filter(|event| {
+            matches!(
+                event,
+                Event::ItemSucceeded { .. } | Event::ItemFailed { .. }
+            )
+        })
+        .collect();
+    // Items are reported as they complete, so the invalid input fails first
+    assert_eq!(
+        items,
+        vec![
+            Event::ItemFailed {
+                index: 1,
+                error: ErrorKind::Validation
+            },
+            Event::ItemSucceeded {
+                index: 0,
+                credits: 1
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_rate_limit_wait_event() {
+    let mut server = Server::new_async().await;
+    server
+        .mock("POST", "/dtc")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "company_name": "TechCorp" }).to_string())
+        .create_async()
+        .await;
+
+    let (sdk, events) = recording_sdk(&server.url(), 0);
+    let sdk = sdk.with_rate_limit(20.0);
+    sdk.dtc("a.com").await.unwrap();
+    sdk.dtc("b.com").await.unwrap();
+
+    let waits: Vec<_> = events
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|event| match event {
+            Event::RateLimitWait { wait, .. } => Some(*wait),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(waits.len(), 1);
+    assert!(waits[0] > Duration::ZERO);
+}
@@ -0,0 +1,78 @@
+use cufinder_rust::{CjaResponse, EncResponse, ExtraFields, Person};
+use serde_json::json;
+
+#[test]
+fn test_unknown_fields_are_captured() {
+    let response: EncResponse = serde_json::from_value(json!({
+        "query": "techcorp.com",
+        "credit_count": 1,
+        "confidence_level": 90,
+        "request_id": "abc-123",
+        "company": {
+            "name": "TechCorp",
+            "employee_count": 250,
+            "hq_timezone": "America/Los_Angeles",
+            "ratings": { "glassdoor": 4.2 }
+        }
+    }))
+    .unwrap();
+
+    assert_eq!(response.base.credit_count, Some(1));
+    assert_eq!(response.base.confidence_level, Some(90));
+    assert_eq!(response.extra.len(), 1);
+    assert_eq!(response.get_extra::<String>("request_id"), Some("abc-123".to_string()));
+
+    let company = &response.company;
+    assert_eq!(company.name, Some("TechCorp".to_string()));
+    assert!(!company.extra.contains_key("name"));
+    assert_eq!(company.get_extra::<String>("hq_timezone"), Some("America/Los_Angeles".to_string()));
+    assert_eq!(company.get_extra::<serde_json::Value>("ratings"), Some(json!({ "glassdoor": 4.2 })));
+    assert_eq!(company.get_extra::<i32>("hq_timezone"), None);
+    assert_eq!(company.get_extra::<String>("missing"), None);
+}
+
+#[test]
+fn test_unknown_fields_round_trip() {
+    let input = json!({
+        "query": { "name": "TechCorp" },
+        "credit_count": 2,
+        "new_top_level_field": [1, 2, 3],
+        "jobs": [
+            {
+                "company": { "name": "TechCorp", "remote_policy": "hybrid" },
+                "job": { "title": "Engineer", "salary_range": { "min": 100, "max": 150 } },
+                "job_score": 0.87
+            }
+        ]
+    });
+
+    let response: CjaResponse = serde_json::from_value(input.clone()).unwrap();
+    assert_eq!(response.get_extra::<Vec<i32>>("new_top_level_field"), Some(vec![1, 2, 3]));
+    assert_eq!(response.jobs[0].get_extra::<f64>("job_score"), Some(0.87));
+    assert_eq!(response.jobs[0].company.get_extra::<String>("remote_policy"), Some("hybrid".to_string()));
+
+    let output = serde_json::to_value(&response).unwrap();
+    assert_eq!(output["new_top_level_field"], input["new_top_level_field"]);
+    assert_eq!(output["jobs"][0]["job_score"], input["jobs"][0]["job_score"]);
+    assert_eq!(output["jobs"][0]["job"]["salary_range"], input["jobs"][0]["job"]["salary_range"]);
+
+    let reparsed: CjaResponse = serde_json::from_value(output).unwrap();
+    assert_eq!(reparsed.extra, response.extra);
+    assert_eq!(reparsed.jobs[0].job.extra, response.jobs[0].job.extra);
+}
+
+#[test]
+fn test_nested_profile_records_keep_unknown_fields() {
+    let person: Person = serde_json::from_value(json!({
+        "full_name": "Jane Smith",
+        "pronouns": "she/her",
+        "experiences": [
+            { "company": "TechCorp", "title": "CTO", "employment_type": "full-time" }
+        ]
+    }))
+    .unwrap();
+
+    assert_eq!(person.get_extra::<String>("pronouns"), Some("she/her".to_string()));
+    let experience = &person.experiences.as_ref().unwrap()[0];
+    assert_eq!(experience.get_extra::<String>("employment_type"), Some("full-time".to_string()));
+}
@@ -0,0 +1,84 @@
+use cufinder_rust::{BoundingBox, CloResponse, GeoPoint, MainLocation, geo::nearest};
+use serde_json::json;
+
+fn offices() -> CloResponse {
+    serde_json::from_value(json!({
+        "locations": [
+            { "city": "San Francisco", "latitude": "37.7749", "longitude": "-122.4194" },
+            { "city": "New York", "latitude": "40.7128", "longitude": "-74.0060" },
+            { "city": "London", "latitude": "51.5074", "longitude": "-0.1278" },
+            { "city": "Unknown", "latitude": "", "longitude": null },
+            { "city": "Invalid", "latitude": "95.0", "longitude": "10.0" }
+        ]
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_geo_point_parse() {
+    assert_eq!(GeoPoint::parse("37.7749,-122.4194"), GeoPoint::new(37.7749, -122.4194));
+    assert_eq!(GeoPoint::parse(" 37.7749, -122.4194 "), GeoPoint::new(37.7749, -122.4194));
+    assert_eq!(GeoPoint::parse("37.7749 -122.4194"), GeoPoint::new(37.7749, -122.4194));
+    assert_eq!(GeoPoint::parse("91,0"), None);
+    assert_eq!(GeoPoint::parse("37.7749"), None);
+    assert_eq!(GeoPoint::parse("north,west"), None);
+
+    let location: MainLocation = serde_json::from_value(json!({ "geo": "51.5074,-0.1278" })).unwrap();
+    assert_eq!(location.geo_point(), GeoPoint::new(51.5074, -0.1278));
+}
+
+#[test]
+fn test_haversine_distance() {
+    let sf = GeoPoint::new(37.7749, -122.4194).unwrap();
+    let ny = GeoPoint::new(40.7128, -74.0060).unwrap();
+
+    let km = sf.distance_km(&ny);
+    assert!((km - 4129.0).abs() < 5.0, "unexpected distance {km}");
+    assert!((sf.distance_miles(&ny) - 2565.0).abs() < 5.0);
+    assert_eq!(sf.distance_km(&sf), 0.0);
+}
+
+#[test]
+fn test_location_filters() {
+    let response = offices();
+    assert_eq!(response.geo_locations().count(), 3);
+
+    let oakland = GeoPoint::new(37.8044, -122.2712).unwrap();
+    let nearby = response.locations_within_km(&oakland, 50.0);
+    assert_eq!(nearby.len(), 1);
+    assert_eq!(nearby[0].city.as_deref(), Some("San Francisco"));
+
+    let us = BoundingBox::new(GeoPoint::new(24.0, -125.0).unwrap(), GeoPoint::new(50.0, -66.0).unwrap());
+    let cities: Vec<_> = response.locations_in(&us).iter().filter_map(|l| l.city.as_deref()).collect();
+    assert_eq!(cities, vec!["San Francisco", "New York"]);
+
+    let (closest, km) = response.nearest_location(&GeoPoint::new(48.8566, 2.3522).unwrap()).unwrap();
+    assert_eq!(closest.city.as_deref(), Some("London"));
+    assert!(km < 400.0);
+}
+
+#[test]
+fn test_bounding_box_around() {
+    let center = GeoPoint::new(0.0, 179.5).unwrap();
+    let bounds = BoundingBox::around(center, 200.0);
+
+    assert!(bounds.min_longitude > bounds.max_longitude);
+    assert!(bounds.contains(&GeoPoint::new(0.0, -179.5).unwrap()));
+    assert!(!bounds.contains(&GeoPoint::new(0.0, 0.0).unwrap()));
+
+    let pole = BoundingBox::around(GeoPoint::new(89.5, 0.0).unwrap(), 100.0);
+    assert!(pole.contains(&GeoPoint::new(89.6, 120.0).unwrap()));
+}
+
+#[test]
+fn test_nearest_region() {
+    let regions = [
+        ("west", GeoPoint::new(37.7749, -122.4194).unwrap()),
+        ("east", GeoPoint::new(40.7128, -74.0060).unwrap()),
+    ];
+    let lead = GeoPoint::new(42.3601, -71.0589).unwrap();
+
+    let (region, _) = nearest(&lead, regions).unwrap();
+    assert_eq!(region, "east");
+    assert!(nearest(&lead, Vec::<(&str, GeoPoint)>::new()).is_none());
+}
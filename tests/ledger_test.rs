@@ -0,0 +1,145 @@
+mod common;
+
+use common::create_test_sdk;
+use cufinder_rust::{
+    CacheConfig, ErrorKind,
+    ledger::{FileLedger, LedgerEntry, LedgerReport, LedgerSink, MemoryLedger},
+};
+use mockito::Server;
+use serde_json::json;
+use std::{io::Write, sync::Arc};
+
+#[tokio::test]
+async fn test_ledger_records_calls_with_tags_and_cache_hits() {
+    let mut server = Server::new_async().await;
+    server
+        .mock("POST", "/dtc")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({ "company_name": "TechCorp", "credit_count": 2, "confidence_level": 90 })
+                .to_string(),
+        )
+        .expect(1)
+        .create_async()
+        .await;
+    server
+        .mock("POST", "/enc")
+        .with_status(404)
+        .with_body("not found")
+        .create_async()
+        .await;
+
+    let ledger = Arc::new(MemoryLedger::new());
+    let sdk = create_test_sdk(&server.url(), 0)
+        .with_cache(CacheConfig::new().negative_ttl(None))
+        .with_ledger(ledger.clone());
+    let sales = sdk.tagged("sales");
+
+    sdk.dtc("techcorp.com").await.unwrap();
+    let cached = sales.dtc("techcorp.com").await.unwrap();
+    assert_eq!(cached.base.credit_count, Some(0));
+    sales.enc("missing.com").await.unwrap_err();
+
+    let entries = ledger.entries();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(
+        (
+            entries[0].credits,
+            entries[0].confidence_level,
+            entries[0].cache_hit
+        ),
+        (2, Some(90), false)
+    );
+    assert_eq!(entries[0].tag, None);
+    assert_eq!((entries[1].credits, entries[1].cache_hit), (0, true));
+    assert_eq!(entries[1].tag.as_deref(), Some("sales"));
+    assert_eq!(entries[2].error, Some(ErrorKind::Api));
+
+    let report = ledger.report();
+    assert_eq!(report.total.calls, 3);
+    assert_eq!(report.total.credits, 2);
+    assert_eq!(report.by_endpoint["/dtc"].calls, 2);
+    assert_eq!(report.by_endpoint["/dtc"].cache_hits, 1);
+    assert_eq!(report.by_tag["sales"].errors, 1);
+    assert_eq!(report.by_tag[""].credits, 2);
+    assert_eq!(report.by_day.len(), 1);
+}
+
+#[test]
+fn test_file_ledger_report_and_csv() {
+    let path = std::env::temp_dir().join(format!("cufinder-ledger-{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let entry = |day: u64, endpoint: &str, credits: i64, tag: Option<&str>| LedgerEntry {
+        timestamp: day * 86_400_000 + 1_000,
+        endpoint: endpoint.to_string(),
+        credits,
+        confidence_level: None,
+        cache_hit: credits == 0,
+        shared: false,
+        tag: tag.map(str::to_string),
+        error: None,
+    };
+
+    let ledger = FileLedger::open(&path).unwrap();
+    ledger
+        .record(&entry(20_000, "/enc", 5, Some("growth, emea")))
+        .unwrap();
+    ledger
+        .record(&entry(20_000, "/enc", 0, Some("growth, emea")))
+        .unwrap();
+    ledger.record(&entry(20_001, "/dtc", 1, None)).unwrap();
+    drop(ledger);
+    std::fs::OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .unwrap()
+        .write_all(b"{\"timestamp\": 17")
+        .unwrap();
+
+    let entries = FileLedger::read(&path).unwrap();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0].day(), "2024-10-04");
+
+    let report = LedgerReport::from_file(&path).unwrap();
+    assert_eq!(report.by_day["2024-10-04"].credits, 5);
+    assert_eq!(report.by_day["2024-10-05"].calls, 1);
+    assert_eq!(
+        report.to_csv(),
+        "day,tag,endpoint,calls,credits,cache_hits,errors\n\
+         2024-10-04,\"growth, emea\",/enc,2,5,1,0\n\
+         2024-10-05,,/dtc,1,1,0,0\n"
+    );
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn test_file_ledger_is_written_on_a_blocking_thread() {
+    let mut server = Server::new_async().await;
+    server
+        .mock("POST", "/dtc")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "company_name": "TechCorp", "credit_count": 1 }).to_string())
+        .create_async()
+        .await;
+
+    let path = std::env::temp_dir().join(format!(
+        "cufinder-ledger-blocking-{}.jsonl",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    let ledger = Arc::new(FileLedger::open(&path).unwrap());
+    assert!(ledger.is_blocking());
+    assert!(!MemoryLedger::new().is_blocking());
+
+    let sdk = create_test_sdk(&server.url(), 0).with_ledger(ledger.clone());
+    sdk.dtc("techcorp.com").await.unwrap();
+    sdk.dtc("techcorp.com").await.unwrap();
+
+    let entries = FileLedger::read(&path).unwrap();
+    assert_eq!(entries.len(), 2);
+    assert!(entries.iter().all(|entry| entry.credits == 1));
+    let _ = std::fs::remove_file(&path);
+}
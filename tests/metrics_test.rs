@@ -0,0 +1,149 @@
+#![cfg(feature = "metrics")]
+
+mod common;
+
+use common::create_test_sdk;
+use cufinder_rust::{
+    CacheConfig, CreditBudget,
+    metrics::{self, MetricsRecorder, PrometheusMetrics},
+};
+use mockito::Server;
+use serde_json::json;
+use std::sync::Arc;
+
+#[tokio::test]
+async fn test_sdk_records_metrics() {
+    let mut server = Server::new_async().await;
+    let busy = server
+        .mock("POST", "/dtc")
+        .with_status(503)
+        .with_header("retry-after", "0")
+        .expect(1)
+        .create_async()
+        .await;
+    let ok = server
+        .mock("POST", "/dtc")
+        .with_status(200)
+        .with_body(json!({ "company_name": "Acme", "credit_count": 2 }).to_string())
+        .expect(1)
+        .create_async()
+        .await;
+    let missing = server
+        .mock("POST", "/lcuf")
+        .with_status(404)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let recorded = Arc::new(PrometheusMetrics::new());
+    let sdk = create_test_sdk(&server.url(), 1)
+        .with_cache(CacheConfig::default())
+        .with_metrics(recorded.clone());
+    sdk.dtc("acme.com").await.unwrap();
+    sdk.dtc("acme.com").await.unwrap();
+    sdk.lcuf("Acme").await.unwrap_err();
+    busy.assert_async().await;
+    ok.assert_async().await;
+    missing.assert_async().await;
+
+    let dtc = [("endpoint", "/dtc")];
+    assert_eq!(recorded.counter(metrics::REQUESTS_TOTAL, &dtc), Some(2.0));
+    assert_eq!(recorded.counter(metrics::CREDITS_TOTAL, &dtc), Some(2.0));
+    assert_eq!(recorded.counter(metrics::CACHE_HITS_TOTAL, &dtc), Some(1.0));
+    assert_eq!(
+        recorded.counter(
+            metrics::RETRIES_TOTAL,
+            &[("endpoint", "/dtc"), ("kind", "api")]
+        ),
+        Some(1.0)
+    );
+    assert_eq!(
+        recorded.counter(
+            metrics::ERRORS_TOTAL,
+            &[("kind", "api"), ("endpoint", "/lcuf")]
+        ),
+        Some(1.0)
+    );
+
+    let text = recorded.render();
+    assert!(text.contains("# TYPE cufinder_requests_total counter\n"));
+    assert!(text.contains("cufinder_requests_total{endpoint=\"/dtc\"} 2\n"));
+    assert!(text.contains("cufinder_errors_total{endpoint=\"/dtc\",kind=\"api\"} 1\n"));
+    assert!(text.contains("# TYPE cufinder_request_duration_seconds histogram\n"));
+    assert!(
+        text.contains(
+            "cufinder_request_duration_seconds_bucket{endpoint=\"/dtc\",le=\"+Inf\"} 2\n"
+        )
+    );
+    assert!(text.contains("cufinder_request_duration_seconds_count{endpoint=\"/lcuf\"} 1\n"));
+}
+
+#[tokio::test]
+async fn test_call_level_failures_count_as_errors() {
+    let mut server = Server::new_async().await;
+    let undecodable = server
+        .mock("POST", "/enc")
+        .with_status(200)
+        .with_body(json!({ "data": { "credit_count": 4, "company": "unexpected" } }).to_string())
+        .expect(1)
+        .create_async()
+        .await;
+
+    let recorded = Arc::new(PrometheusMetrics::new());
+    let sdk = create_test_sdk(&server.url(), 0)
+        .with_budget(CreditBudget::new().hard_limit(4))
+        .with_metrics(recorded.clone());
+    sdk.dtc("").await.unwrap_err();
+    sdk.enc("acme.com").await.unwrap_err();
+    sdk.enc("other.com").await.unwrap_err();
+    undecodable.assert_async().await;
+
+    let offline = create_test_sdk(&server.url(), 0)
+        .with_cache(CacheConfig::new().offline(true))
+        .with_metrics(recorded.clone());
+    offline.dtc("acme.com").await.unwrap_err();
+
+    let errors = |endpoint, kind| {
+        recorded.counter(
+            metrics::ERRORS_TOTAL,
+            &[("endpoint", endpoint), ("kind", kind)],
+        )
+    };
+    assert_eq!(errors("/dtc", "validation"), Some(1.0));
+    assert_eq!(errors("/enc", "json"), Some(1.0));
+    assert_eq!(errors("/enc", "budget"), Some(1.0));
+    assert_eq!(errors("/dtc", "cache_miss"), Some(1.0));
+    // Only the undecodable response was an HTTP request
+    assert_eq!(
+        recorded.counter(metrics::REQUESTS_TOTAL, &[("endpoint", "/enc")]),
+        Some(1.0)
+    );
+    assert_eq!(
+        recorded.counter(metrics::REQUESTS_TOTAL, &[("endpoint", "/dtc")]),
+        None
+    );
+}
+
+#[test]
+fn test_prometheus_rendering() {
+    let recorded = PrometheusMetrics::with_buckets(vec![1.0, 0.1]);
+    for latency in [0.05, 0.5, 2.0] {
+        recorded.observe("job_seconds", &[("queue", "a\"b")], latency);
+    }
+    recorded.increment("jobs_total", &[], 3.0);
+
+    assert_eq!(
+        recorded.render(),
+        "# TYPE jobs_total counter\n\
+         jobs_total 3\n\
+         # TYPE job_seconds histogram\n\
+         job_seconds_bucket{queue=\"a\\\"b\",le=\"0.1\"} 1\n\
+         job_seconds_bucket{queue=\"a\\\"b\",le=\"1\"} 2\n\
+         job_seconds_bucket{queue=\"a\\\"b\",le=\"+Inf\"} 3\n\
+         job_seconds_sum{queue=\"a\\\"b\"} 2.55\n\
+         job_seconds_count{queue=\"a\\\"b\"} 3\n"
+    );
+
+    recorded.reset();
+    assert_eq!(recorded.render(), "");
+}
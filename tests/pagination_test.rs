@@ -0,0 +1,271 @@
+mod common;
+
+use common::create_test_sdk;
+use cufinder_rust::{
+    CseParams, CseResponse, CufinderError, Page, PagedResponse, PaginationOptions,
+};
+use futures::{StreamExt, TryStreamExt};
+use mockito::{Matcher, Server, ServerGuard};
+use serde_json::{Value, json};
+
+fn companies(names: &[&str]) -> Value {
+    Value::Array(names.iter().map(|name| json!({ "name": name })).collect())
+}
+
+fn mock_page(server: &mut ServerGuard, path: &str, page: i32, body: Value) -> mockito::Mock {
+    server
+        .mock("POST", path)
+        .match_body(Matcher::UrlEncoded("page".to_string(), page.to_string()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(body.to_string())
+}
+
+#[tokio::test]
+async fn test_cse_stream_stops_on_short_page() {
+    let mut server = Server::new_async().await;
+    let page1 = mock_page(
+        &mut server,
+        "/cse",
+        1,
+        json!({ "companies": companies(&["A", "B"]) }),
+    )
+    .create_async()
+    .await;
+    let page2 = mock_page(
+        &mut server,
+        "/cse",
+        2,
+        json!({ "companies": companies(&["C", "D"]) }),
+    )
+    .create_async()
+    .await;
+    let page3 = mock_page(
+        &mut server,
+        "/cse",
+        3,
+        json!({ "companies": companies(&["E"]) }),
+    )
+    .create_async()
+    .await;
+    let page4 = mock_page(&mut server, "/cse", 4, json!({ "companies": [] }))
+        .expect(0)
+        .create_async()
+        .await;
+
+    let sdk = create_test_sdk(&server.url(), 1);
+    let params = CseParams::builder().country("US").build().unwrap();
+    let names: Vec<String> = sdk
+        .cse_stream(params, PaginationOptions::new())
+        .map_ok(|company| company.name.unwrap())
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(names, vec!["A", "B", "C", "D", "E"]);
+    page1.assert_async().await;
+    page2.assert_async().await;
+    page3.assert_async().await;
+    page4.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_stream_stops_on_empty_page_and_meta_marker() {
+    let mut server = Server::new_async().await;
+    let _p1 = mock_page(
+        &mut server,
+        "/cef",
+        1,
+        json!({ "employees": [{ "full_name": "A" }] }),
+    )
+    .create_async()
+    .await;
+    let _p2 = mock_page(&mut server, "/cef", 2, json!({ "employees": [] }))
+        .create_async()
+        .await;
+    let _a1 = mock_page(
+        &mut server,
+        "/caa",
+        1,
+        json!({ "activities": [{ "activity_id": "1" }], "meta_data": { "total_pages": 1 } }),
+    )
+    .create_async()
+    .await;
+    let a2 = mock_page(
+        &mut server,
+        "/caa",
+        2,
+        json!({ "activities": [{ "activity_id": "2" }] }),
+    )
+    .expect(0)
+    .create_async()
+    .await;
+
+    let sdk = create_test_sdk(&server.url(), 1);
+    let employees: Vec<_> = sdk
+        .cef_stream("TechCorp", PaginationOptions::new())
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(employees.len(), 1);
+
+    let activities: Vec<_> = sdk
+        .caa_stream("TechCorp", PaginationOptions::new())
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(activities.len(), 1);
+    a2.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_stream_limits_and_resume() {
+    let mut server = Server::new_async().await;
+    let p2 = mock_page(
+        &mut server,
+        "/lbs",
+        2,
+        json!({ "companies": companies(&["C", "D"]) }),
+    )
+    .expect(2)
+    .create_async()
+    .await;
+    let p3 = mock_page(
+        &mut server,
+        "/lbs",
+        3,
+        json!({ "companies": companies(&["E", "F"]) }),
+    )
+    .expect(2)
+    .create_async()
+    .await;
+    let p4 = mock_page(
+        &mut server,
+        "/lbs",
+        4,
+        json!({ "companies": companies(&["G", "H"]) }),
+    )
+    .expect(0)
+    .create_async()
+    .await;
+
+    let sdk = create_test_sdk(&server.url(), 1);
+
+    let options = PaginationOptions::new().start_page(2).max_pages(2);
+    let all: Vec<_> = sdk
+        .lbs_stream(Default::default(), options)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(all.len(), 4);
+
+    let options = PaginationOptions::new().start_page(2).max_items(3);
+    let capped: Vec<_> = sdk
+        .lbs_stream(Default::default(), options)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(capped.len(), 3);
+
+    p2.assert_async().await;
+    p3.assert_async().await;
+    p4.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_stream_yields_error_and_ends() {
+    let mut server = Server::new_async().await;
+    let _p1 = mock_page(
+        &mut server,
+        "/pse",
+        1,
+        json!({ "peoples": [{ "full_name": "A" }] }),
+    )
+    .create_async()
+    .await;
+    let _p2 = server
+        .mock("POST", "/pse")
+        .match_body(Matcher::UrlEncoded("page".to_string(), "2".to_string()))
+        .with_status(500)
+        .with_body("boom")
+        .create_async()
+        .await;
+
+    let sdk = create_test_sdk(&server.url(), 1);
+    let results: Vec<_> = sdk
+        .pse_stream(Default::default(), PaginationOptions::new())
+        .collect()
+        .await;
+
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_ok());
+    assert!(matches!(
+        results[1],
+        Err(CufinderError::ApiError { status: 500, .. })
+    ));
+}
+
+#[test]
+fn test_page_from_response_metadata() {
+    let response: CseResponse = serde_json::from_value(json!({
+        "companies": companies(&["A", "B"]),
+        "meta_data": { "total": 5, "page_size": 2 }
+    }))
+    .unwrap();
+    let params = CseParams::builder().country("US").page(2).build().unwrap();
+
+    let page = response.into_page(&params);
+    assert_eq!(page.page, 2);
+    assert_eq!(page.page_size, 2);
+    assert_eq!(page.total, Some(5));
+    assert!(page.has_next());
+
+    let next = page.next_params(&params).unwrap();
+    assert_eq!(next.page, Some(3));
+    assert_eq!(next.country.as_deref(), Some("US"));
+
+    let last = Page::new(vec!["E"], page.meta_data.clone(), 3);
+    assert!(!last.has_next());
+    assert!(last.next_params(&params).is_none());
+}
+
+#[test]
+fn test_page_has_next_markers() {
+    let page = |items: Vec<i32>, meta: Value| Page::new(items, Some(meta), 1);
+
+    assert!(!page(vec![1, 2], json!({ "has_more": false })).has_next());
+    assert!(page(vec![1, 2], json!({ "has_next": true })).has_next());
+    assert!(!page(vec![1, 2], json!({ "total_pages": 1 })).has_next());
+    assert!(page(vec![1, 2], json!({ "current_page": "1", "last_page": "3" })).has_next());
+    assert!(!page(vec![1, 2], json!({ "next_page": null })).has_next());
+    assert!(!page(vec![1], json!({ "per_page": 10 })).has_next());
+    assert!(!Page::<i32>::new(vec![], None, 1).has_next());
+
+    // A short last page does not stand in for the page size when comparing the total
+    let short = |page| Page::new(vec![1; 5], Some(json!({ "total": 25 })), page);
+    assert!(short(1).has_next());
+    assert!(!short(3).with_page_size(10).has_next());
+    assert!(
+        short(3)
+            .with_page_size(10)
+            .next_params(&CseParams::default())
+            .is_none()
+    );
+    assert!(short(2).with_page_size(10).has_next());
+    assert!(
+        !short(5).has_next(),
+        "5 pages of at least 5 items reach the total"
+    );
+    assert!(
+        !page(
+            vec![1; 5],
+            json!({ "total": 25, "page_size": 10, "page": 3 })
+        )
+        .has_next()
+    );
+
+    // Without metadata a full page may be followed by another one
+    let unknown = Page::new(vec![1, 2], None, 4);
+    assert_eq!(unknown.next_page(), Some(5));
+    assert_eq!(unknown.into_iter().sum::<i32>(), 3);
+}
@@ -0,0 +1,125 @@
+use cufinder_rust::{PartialDate, Person, PseResponse};
+use serde_json::json;
+
+fn sample_person() -> Person {
+    serde_json::from_value(json!({
+        "full_name": "Jane Smith",
+        "connections": "500+",
+        "experiences": [
+            {
+                "company_name": "TechCorp",
+                "title": "VP Engineering",
+                "start_date": "2021-06",
+                "end_date": null,
+                "is_current": true
+            },
+            {
+                "company": { "name": "TechCorp" },
+                "job_title": "Engineering Manager",
+                "start_date": "Jan 2019",
+                "end_date": "2021-06",
+                "is_current": "false"
+            },
+            {
+                "company": "DataCorp",
+                "title": "Software Engineer",
+                "starts_at": { "year": 2015, "month": 3 },
+                "ends_at": { "year": 2018, "month": 12 }
+            },
+            {
+                "company": "StartupCo",
+                "title": "Intern",
+                "start_date": 2014,
+                "end_date": "08/2014"
+            },
+            "not an experience"
+        ],
+        "educations": {
+            "school_name": "MIT",
+            "degree": "BSc",
+            "field": "Computer Science",
+            "start_date": 2010,
+            "end_date": 2014
+        },
+        "certifications": [
+            { "title": "AWS Solutions Architect", "issuer": "Amazon", "issued_at": "2020-01-15" }
+        ]
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_person_typed_records() {
+    let person = sample_person();
+
+    assert_eq!(person.connections, Some(json!("500+")));
+    assert_eq!(person.connection_count(), Some(500));
+
+    let experiences = person.experiences.as_ref().unwrap();
+    assert_eq!(experiences.len(), 4);
+    assert_eq!(experiences[1].company, Some("TechCorp".to_string()));
+    assert_eq!(experiences[1].title, Some("Engineering Manager".to_string()));
+    assert_eq!(experiences[1].is_current, Some(false));
+    assert_eq!(experiences[2].start_date, Some("2015-03".to_string()));
+    assert_eq!(experiences[3].start_date, Some("2014".to_string()));
+
+    let educations = person.educations.as_ref().unwrap();
+    assert_eq!(educations.len(), 1);
+    assert_eq!(educations[0].school, Some("MIT".to_string()));
+    assert_eq!(educations[0].field_of_study, Some("Computer Science".to_string()));
+
+    let certifications = person.certifications.as_ref().unwrap();
+    assert_eq!(certifications[0].name, Some("AWS Solutions Architect".to_string()));
+    assert_eq!(certifications[0].authority, Some("Amazon".to_string()));
+}
+
+#[test]
+fn test_person_helpers() {
+    let person = sample_person();
+
+    let current = person.current_role().unwrap();
+    assert_eq!(current.title, Some("VP Engineering".to_string()));
+
+    assert_eq!(person.previous_employers(), vec!["DataCorp", "StartupCo"]);
+
+    let as_of = PartialDate { year: 2024, month: Some(6) };
+    // Jan 2019 to Jun 2024, the two TechCorp roles are contiguous
+    assert_eq!(person.tenure_at_as_of("techcorp", as_of), Some(65));
+    assert_eq!(person.tenure_at_as_of("DataCorp", as_of), Some(45));
+    assert_eq!(person.tenure_at_as_of("Unknown", as_of), None);
+}
+
+#[test]
+fn test_partial_date_parse() {
+    let date = |year, month| Some(PartialDate { year, month });
+
+    assert_eq!(PartialDate::parse("2020"), date(2020, None));
+    assert_eq!(PartialDate::parse("2020-03-15T10:00:00Z"), date(2020, Some(3)));
+    assert_eq!(PartialDate::parse("03/2020"), date(2020, Some(3)));
+    assert_eq!(PartialDate::parse("September 2021"), date(2021, Some(9)));
+    assert_eq!(PartialDate::parse("Present"), None);
+    assert_eq!(PartialDate::parse(""), None);
+}
+
+#[test]
+fn test_pse_response_with_untyped_profile_fields() {
+    let response: PseResponse = serde_json::from_value(json!({
+        "peoples": [
+            {
+                "full_name": "John Doe",
+                "experience": { "company": "TechCorp", "title": "CTO" },
+                "connections": 1200,
+                "experiences": null,
+                "certifications": "n/a"
+            }
+        ],
+        "credit_count": 1
+    }))
+    .unwrap();
+
+    let person = &response.peoples[0];
+    assert_eq!(person.connection_count(), Some(1200));
+    assert_eq!(person.experiences.as_ref().map(Vec::len), None);
+    assert_eq!(person.certifications.as_ref().map(Vec::len), None);
+    assert_eq!(person.current_role().unwrap().title, Some("CTO".to_string()));
+}
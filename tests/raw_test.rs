@@ -0,0 +1,163 @@
+mod common;
+
+use common::create_test_sdk;
+use cufinder_rust::{CacheConfig, CufinderError};
+use mockito::{Matcher, Server};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Serialize)]
+struct InsightParams<'a> {
+    domain: &'a str,
+    depth: u8,
+}
+
+#[derive(Debug, Deserialize)]
+struct Insight {
+    score: f64,
+    credit_count: i32,
+}
+
+fn envelope() -> String {
+    json!({
+        "status": 1,
+        "data": { "score": 0.75, "credit_count": 3 },
+        "meta_data": { "query_id": "abc" }
+    })
+    .to_string()
+}
+
+#[tokio::test]
+async fn test_call_raw_returns_untouched_response() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/insights")
+        .match_body(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("domain".into(), "acme.com".into()),
+            Matcher::UrlEncoded("depth".into(), "2".into()),
+        ]))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("x-request-id", "req-42")
+        .with_body(envelope())
+        .create_async()
+        .await;
+
+    let sdk = create_test_sdk(&server.url(), 0);
+    let params = InsightParams {
+        domain: "acme.com",
+        depth: 2,
+    };
+    let raw = sdk.call_raw("/insights", &params).await.unwrap();
+
+    assert_eq!(raw.status, 200);
+    assert_eq!(raw.headers["x-request-id"], "req-42");
+    assert_eq!(raw.body, envelope());
+    assert_eq!(
+        raw.data,
+        json!({ "score": 0.75, "credit_count": 3, "meta_data": { "query_id": "abc" } })
+    );
+    assert_eq!(sdk.credits().spent(), 3);
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_call_raw_retries_and_maps_errors() {
+    let mut server = Server::new_async().await;
+    let unavailable = server
+        .mock("POST", "/insights")
+        .with_status(503)
+        .with_header("retry-after", "0")
+        .with_body("busy")
+        .expect(1)
+        .create_async()
+        .await;
+    let ok = server
+        .mock("POST", "/insights")
+        .with_status(200)
+        .with_body(envelope())
+        .expect(1)
+        .create_async()
+        .await;
+
+    let sdk = create_test_sdk(&server.url(), 1);
+    let raw = sdk
+        .call_raw("/insights", [("domain", "acme.com")])
+        .await
+        .unwrap();
+    assert_eq!(raw.data["score"], 0.75);
+    unavailable.assert_async().await;
+    ok.assert_async().await;
+
+    let unauthorized = server
+        .mock("POST", "/secret")
+        .with_status(401)
+        .with_body("bad key")
+        .create_async()
+        .await;
+    let error = sdk
+        .call_raw("/secret", [("domain", "acme.com")])
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, CufinderError::AuthenticationError(ref message) if message == "bad key")
+    );
+    unauthorized.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_call_json_decodes_unwrapped_data() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/insights")
+        .with_status(200)
+        .with_body(envelope())
+        .expect(3)
+        .create_async()
+        .await;
+
+    let sdk = create_test_sdk(&server.url(), 0).with_cache(CacheConfig::default());
+    let mut charged = Vec::new();
+    for _ in 0..2 {
+        let insight: Insight = sdk
+            .call_json("/insights", [("domain", "acme.com")])
+            .await
+            .unwrap();
+        assert_eq!(insight.score, 0.75);
+        charged.push(insight.credit_count);
+    }
+    // Dynamic calls bypass the cache like `call_raw`, so both are sent and charged
+    assert_eq!(charged, vec![3, 3]);
+    assert_eq!(sdk.credits().spent(), 6);
+
+    let error = sdk
+        .call_json::<Vec<String>>("/insights", [("domain", "acme.com")])
+        .await
+        .unwrap_err();
+    assert!(matches!(error, CufinderError::JsonError(_)));
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_raw_calls_are_not_sent_offline() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/insights")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let sdk = create_test_sdk(&server.url(), 0).with_cache(CacheConfig::new().offline(true));
+    let error = sdk
+        .call_raw("/insights", [("domain", "acme.com")])
+        .await
+        .unwrap_err();
+    assert!(matches!(error, CufinderError::CacheMiss(_)));
+    let error = sdk
+        .call_json::<Insight>("/insights", [("domain", "acme.com")])
+        .await
+        .unwrap_err();
+    assert!(matches!(error, CufinderError::CacheMiss(_)));
+    assert_eq!(sdk.credits().spent(), 0);
+    mock.assert_async().await;
+}
@@ -0,0 +1,185 @@
+#![cfg(feature = "tracing")]
+
+mod common;
+
+use common::create_test_sdk;
+use cufinder_rust::CacheConfig;
+use mockito::Server;
+use serde_json::json;
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+};
+use tracing::{
+    Event, Metadata, Subscriber,
+    field::{Field, Visit},
+    span,
+};
+
+type Fields = HashMap<String, String>;
+
+/// Keeps every span's fields and every event, for assertions
+#[derive(Clone, Default)]
+struct Recorder {
+    spans: Arc<Mutex<Vec<(&'static Metadata<'static>, Fields)>>>,
+    events: Arc<Mutex<Vec<Fields>>>,
+}
+
+struct FieldVisitor<'a>(&'a mut Fields);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attributes: &span::Attributes<'_>) -> span::Id {
+        let mut fields = Fields::new();
+        attributes.record(&mut FieldVisitor(&mut fields));
+        let mut spans = self.spans.lock().unwrap();
+        spans.push((attributes.metadata(), fields));
+        span::Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, id: &span::Id, values: &span::Record<'_>) {
+        let mut spans = self.spans.lock().unwrap();
+        let (_, fields) = &mut spans[id.into_u64() as usize - 1];
+        values.record(&mut FieldVisitor(fields));
+    }
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields::new();
+        event.record(&mut FieldVisitor(&mut fields));
+        self.events.lock().unwrap().push(fields);
+    }
+
+    fn enter(&self, _: &span::Id) {}
+
+    fn exit(&self, _: &span::Id) {}
+}
+
+impl Recorder {
+    fn spans(&self) -> Vec<Fields> {
+        let spans = self.spans.lock().unwrap();
+        spans
+            .iter()
+            .filter(|(metadata, _)| metadata.name() == "cufinder_request")
+            .map(|(_, fields)| fields.clone())
+            .collect()
+    }
+
+    fn messages(&self) -> Vec<String> {
+        let events = self.events.lock().unwrap();
+        events
+            .iter()
+            .filter_map(|e| e.get("message").cloned())
+            .collect()
+    }
+}
+
+/// Run `test` on a single-threaded runtime with `recorder` as the subscriber
+fn traced<F: Future<Output = ()>>(recorder: &Recorder, test: impl FnOnce() -> F) {
+    tracing::subscriber::with_default(recorder.clone(), || {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(test())
+    });
+}
+
+#[test]
+fn test_span_fields_and_retry_events() {
+    let recorder = Recorder::default();
+    traced(&recorder, || async {
+        let mut server = Server::new_async().await;
+        let busy = server
+            .mock("POST", "/dtc")
+            .with_status(503)
+            .with_header("retry-after", "0")
+            .expect(1)
+            .create_async()
+            .await;
+        let ok = server
+            .mock("POST", "/dtc")
+            .with_status(200)
+            .with_body(json!({ "company_name": "Acme", "credit_count": 2 }).to_string())
+            .expect(2)
+            .create_async()
+            .await;
+
+        let sdk = create_test_sdk(&server.url(), 1)
+            .with_rate_limit(1000.0)
+            .with_cache(CacheConfig::default());
+        sdk.dtc("acme.com").await.unwrap();
+        sdk.dtc("acme.com").await.unwrap();
+        busy.assert_async().await;
+        drop(ok);
+    });
+
+    let spans = recorder.spans();
+    assert_eq!(spans.len(), 2);
+    let first = &spans[0];
+    assert_eq!(first["endpoint"], "/dtc");
+    assert_eq!(first["attempt"], "1");
+    assert_eq!(first["status"], "200");
+    assert_eq!(first["credit_count"], "2");
+    assert_eq!(first["cache"], "miss");
+    assert!(first.contains_key("latency_ms"));
+    assert!(!first.contains_key("error"));
+
+    let second = &spans[1];
+    assert_eq!(second["cache"], "hit");
+    assert_eq!(second["credit_count"], "0");
+    assert!(!second.contains_key("status"));
+
+    let messages = recorder.messages();
+    assert!(messages.iter().any(|m| m == "retrying CUFinder request"));
+
+    // Neither the API key nor the request parameters are recorded by default
+    for fields in spans.iter().chain(recorder.events.lock().unwrap().iter()) {
+        assert!(!fields.contains_key("params"));
+        for value in fields.values() {
+            assert!(!value.contains("test-api-key"));
+            assert!(!value.contains("acme.com"));
+        }
+    }
+}
+
+#[test]
+fn test_params_and_errors_recorded_when_enabled() {
+    let recorder = Recorder::default();
+    traced(&recorder, || async {
+        let mut server = Server::new_async().await;
+        let missing = server
+            .mock("POST", "/dtc")
+            .with_status(404)
+            .with_body("not found")
+            .create_async()
+            .await;
+
+        let sdk = create_test_sdk(&server.url(), 1).with_trace_params(true);
+        sdk.dtc("acme.com").await.unwrap_err();
+        missing.assert_async().await;
+    });
+
+    let spans = recorder.spans();
+    assert_eq!(spans.len(), 1);
+    assert_eq!(spans[0]["params"], "company_website=acme.com");
+    assert_eq!(spans[0]["status"], "404");
+    assert_eq!(spans[0]["error"], "api");
+    assert_eq!(spans[0]["cache"], "bypass");
+}